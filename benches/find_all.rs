@@ -0,0 +1,141 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (C) 2022 Scott MacDonald.
+////////////////////////////////////////////////////////////////////////////////
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+////////////////////////////////////////////////////////////////////////////////
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use spellingbee::{check_word, find_all, find_all_ci, DictionaryIndex};
+
+/// Small sample of real English words bundled with the crate so results are
+/// reproducible on machines without a system dictionary.
+const SAMPLE_WORDS: &str = include_str!("../data/words.txt");
+
+/// Realistic puzzle letters used by every benchmark.
+const REQUIRED: char = 'o';
+const EXTRA: &str = "unrlap";
+
+/// Version of `EXTRA` with a letter outside of ASCII.
+const NON_ASCII_EXTRA: &str = "unrl\u{e1}p";
+
+/// Generate `count` pseudo random lowercase words between 3 and 12 letters
+/// long. A fixed seed keeps the word list identical between runs.
+fn generate_words(count: usize) -> Vec<String> {
+    let mut seed: u32 = 0x2545_f491;
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed
+    };
+
+    (0..count)
+        .map(|_| {
+            let len = 3 + (next() % 10) as usize;
+            (0..len)
+                .map(|_| (b'a' + (next() % 26) as u8) as char)
+                .collect()
+        })
+        .collect()
+}
+
+/// Load the bundled sample word list.
+fn sample_words() -> Vec<&'static str> {
+    SAMPLE_WORDS.lines().collect()
+}
+
+fn bench_find_all(c: &mut Criterion) {
+    let words = generate_words(100_000);
+    let sample = sample_words();
+
+    // `find_all` checks every word with the bitmask fast path, while calling
+    // `check_word` for each word compares characters one at a time.
+    c.bench_function("find_all (bitmask)", |b| {
+        b.iter(|| find_all(black_box(&words), REQUIRED, EXTRA))
+    });
+
+    c.bench_function("check_word (scalar)", |b| {
+        b.iter(|| {
+            black_box(&words)
+                .iter()
+                .filter_map(|w| check_word(w, REQUIRED, EXTRA))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    // The index computes each word's letter mask once up front, which is what
+    // makes solving many puzzles against the same dictionary cheap.
+    let index = DictionaryIndex::new(&words);
+    c.bench_function("DictionaryIndex::find_all", |b| {
+        b.iter(|| black_box(&index).find_all(REQUIRED, EXTRA))
+    });
+
+    c.bench_function("find_all sample words (bitmask)", |b| {
+        b.iter(|| find_all(black_box(&sample), REQUIRED, EXTRA))
+    });
+
+    c.bench_function("check_word sample words (scalar)", |b| {
+        b.iter(|| {
+            black_box(&sample)
+                .iter()
+                .filter_map(|w| check_word(w, REQUIRED, EXTRA))
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
+fn bench_check_word(c: &mut Criterion) {
+    // A pangram has to be checked against every letter, while most dictionary
+    // words are rejected early because of a letter outside the puzzle.
+    c.bench_function("check_word hit", |b| {
+        b.iter(|| check_word(black_box("unpopular"), REQUIRED, EXTRA))
+    });
+
+    c.bench_function("check_word miss", |b| {
+        b.iter(|| check_word(black_box("question"), REQUIRED, EXTRA))
+    });
+
+    c.bench_function("check_word miss (no required letter)", |b| {
+        b.iter(|| check_word(black_box("plural"), REQUIRED, EXTRA))
+    });
+}
+
+fn bench_non_ascii(c: &mut Criterion) {
+    // Puzzles with letters outside of ASCII cannot use letter masks, so every
+    // word is checked one character at a time against the puzzle letters.
+    // `find_all` prepares the puzzle letters once, while `check_word` prepares
+    // them for every word.
+    let words: Vec<String> = generate_words(100_000)
+        .iter()
+        .map(|w| w.replace('a', "\u{e1}"))
+        .collect();
+
+    c.bench_function("check_word non-ASCII puzzle", |b| {
+        b.iter(|| {
+            black_box(&words)
+                .iter()
+                .filter_map(|w| check_word(w, REQUIRED, NON_ASCII_EXTRA))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    c.bench_function("find_all non-ASCII puzzle", |b| {
+        b.iter(|| find_all(black_box(&words), REQUIRED, NON_ASCII_EXTRA))
+    });
+
+    c.bench_function("find_all_ci non-ASCII puzzle", |b| {
+        b.iter(|| find_all_ci(black_box(&words), REQUIRED, NON_ASCII_EXTRA))
+    });
+}
+
+criterion_group!(benches, bench_find_all, bench_check_word, bench_non_ascii);
+criterion_main!(benches);
@@ -13,210 +13,5605 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 ////////////////////////////////////////////////////////////////////////////////
-const PANGRAM_SCORE_BOOST: i32 = 7;
-const SCORE_MIN_LENGTH: usize = 5;
-const WORD_MIN_LENGTH: usize = 4;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+//! Find answers to the NYT spelling bee game.
+//!
+//! The `std` feature is enabled by default. Without it the crate only needs
+//! `alloc`, which allows the word checking core to run on embedded targets.
+//! Reading dictionaries and the other APIs that need the standard library are
+//! only available with the `std` feature.
+
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::io::{self, BufRead};
+
+/// Extra points a pangram is worth with the default `Scoring`.
+pub const PANGRAM_BONUS: i32 = 7;
+
+/// Words with at least this many letters score one point per letter with the
+/// default `Scoring`, and shorter answers score one point.
+pub const LONG_WORD_MIN_LENGTH: usize = 5;
+
+/// The fewest letters an answer can have with the default `Scoring`.
+pub const MIN_WORD_LENGTH: usize = 4;
+
+/// Number of letters in a standard puzzle, including the required letter.
+pub const PUZZLE_LETTER_COUNT: usize = 7;
 
 /// Holds details for a word that is considered an answer to the spelling bee
 /// setup.
-#[derive(Debug, PartialEq)]
+///
+/// Answers are ordered "best first": by descending score, then pangrams before
+/// other answers, and finally alphabetically by word. Sorting a list of answers
+/// with `sort()` puts the highest scoring answer at the front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Answer {
     pub word: String,
     pub score: i32,
     pub is_pangram: bool,
 }
 
-/// Finds all spelling bee answers from an iterable list of words.
-pub fn find_all<I, S>(words: I, required: char, extra: &str) -> Vec<Answer>
-where
-    I: IntoIterator<Item = S>,
-    S: AsRef<str>,
-{
-    // IntoIterator inspiration from: https://stackoverflow.com/a/35626785
-    let mut answers: Vec<Answer> = Vec::new();
+impl Ord for Answer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .score
+            .cmp(&self.score)
+            .then_with(|| other.is_pangram.cmp(&self.is_pangram))
+            .then_with(|| self.word.cmp(&other.word))
+    }
+}
+
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Answer {
+    /// Formats the answer as its score followed by the word, with pangrams
+    /// marked by a leading `*`. For example `* 12 motel` or `  1  tome`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = if self.is_pangram { '*' } else { ' ' };
+        write!(f, "{} {:<2} {}", marker, self.score, self.word)
+    }
+}
+
+/// An answer that borrows its word from the dictionary rather than owning it.
+/// See `check_word_ref`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AnswerRef<'a> {
+    pub word: &'a str,
+    pub score: i32,
+    pub is_pangram: bool,
+}
+
+impl AnswerRef<'_> {
+    /// Copy this answer into an `Answer` that owns its word.
+    pub fn to_owned(self) -> Answer {
+        Answer {
+            word: self.word.to_string(),
+            score: self.score,
+            is_pangram: self.is_pangram,
+        }
+    }
+}
+
+/// Totals computed over a set of answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Summary {
+    /// Number of answers.
+    pub word_count: usize,
+    /// Number of answers that are pangrams.
+    pub pangram_count: usize,
+    /// Sum of every answer's score.
+    pub total_score: i32,
+}
+
+/// Count the answers and pangrams in `answers`, and total up their scores.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, summarize};
+/// let answers = find_all(["loon", "pronoun", "unpopular"], 'o', "unrlap");
+/// let summary = summarize(&answers);
+///
+/// assert_eq!(3, summary.word_count);
+/// assert_eq!(1, summary.pangram_count);
+/// assert_eq!(24, summary.total_score);
+/// ```
+pub fn summarize(answers: &[Answer]) -> Summary {
+    let mut summary = Summary::default();
+
+    for ans in answers {
+        summary.word_count += 1;
+        summary.total_score += ans.score;
+
+        if ans.is_pangram {
+            summary.pangram_count += 1;
+        }
+    }
+
+    summary
+}
+
+/// Get every answer in `answers` whose word starts with `prefix`. Letter case
+/// must match, and an empty prefix matches every answer.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{filter_prefix, find_all};
+/// let answers = find_all(["loon", "pronoun", "poll", "roll"], 'o', "unrlap");
+///
+/// assert_eq!(2, filter_prefix(&answers, "p").len());
+/// assert_eq!("roll", filter_prefix(&answers, "ro")[0].word);
+/// ```
+pub fn filter_prefix<'a>(answers: &'a [Answer], prefix: &str) -> Vec<&'a Answer> {
+    answers
+        .iter()
+        .filter(|a| a.word.starts_with(prefix))
+        .collect()
+}
+
+/// Version of `filter_prefix` that ignores letter case, for answers found with
+/// `find_all_ci`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{filter_prefix_ci, find_all_ci};
+/// let answers = find_all_ci(["Loon", "pronoun", "Poll"], 'o', "unrlap");
+///
+/// assert_eq!(2, filter_prefix_ci(&answers, "P").len());
+/// ```
+pub fn filter_prefix_ci<'a>(answers: &'a [Answer], prefix: &str) -> Vec<&'a Answer> {
+    let prefix = prefix.to_lowercase();
+
+    answers
+        .iter()
+        .filter(|a| a.word.to_lowercase().starts_with(&prefix))
+        .collect()
+}
+
+/// Get every answer in `answers` whose word is exactly `len` letters long.
+/// Length is measured in characters rather than bytes.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{filter_length, find_all};
+/// let answers = find_all(["loon", "pronoun", "poll", "roll"], 'o', "unrlap");
+///
+/// assert_eq!(3, filter_length(&answers, 4).len());
+/// assert_eq!("pronoun", filter_length(&answers, 7)[0].word);
+/// ```
+pub fn filter_length(answers: &[Answer], len: usize) -> Vec<&Answer> {
+    answers
+        .iter()
+        .filter(|a| a.word.chars().count() == len)
+        .collect()
+}
+
+/// Get every answer in `answers` that is a pangram.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, pangrams};
+/// let answers = find_all(["loon", "pronoun", "unpopular"], 'o', "unrlap");
+///
+/// assert_eq!("unpopular", pangrams(&answers)[0].word);
+/// ```
+pub fn pangrams(answers: &[Answer]) -> Vec<&Answer> {
+    answers.iter().filter(|a| a.is_pangram).collect()
+}
+
+/// Percentage of the total score needed to reach the "Genius" rank.
+const GENIUS_PERCENT: i32 = 70;
+
+/// Sum the scores of every answer in `answers`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, total_score};
+/// let answers = find_all(["loon", "pronoun", "unpopular"], 'o', "unrlap");
+///
+/// assert_eq!(24, total_score(&answers));
+/// ```
+pub fn total_score(answers: &[Answer]) -> i32 {
+    answers.iter().map(|a| a.score).sum()
+}
+
+/// Sum the scores of `answers` in the order they were found, adding
+/// `first_word_bonus` for the first answer. No bonus is given when there are no
+/// answers.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, score_sequence};
+/// let answers = find_all(["loon", "pronoun", "unpopular"], 'o', "unrlap");
+///
+/// assert_eq!(27, score_sequence(&answers, 3));
+/// ```
+pub fn score_sequence(answers: &[Answer], first_word_bonus: i32) -> i32 {
+    let bonus = if answers.is_empty() {
+        0
+    } else {
+        first_word_bonus
+    };
+
+    total_score(answers) + bonus
+}
+
+/// Get the score needed to reach the "Genius" rank, which is 70% of the total
+/// score of `answers` rounded up.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, genius_threshold};
+/// let answers = find_all(["loon", "pronoun", "unpopular"], 'o', "unrlap");
+///
+/// assert_eq!(17, genius_threshold(&answers));
+/// ```
+pub fn genius_threshold(answers: &[Answer]) -> i32 {
+    // Integer math rounds up without the error that comes with floats.
+    (total_score(answers) * GENIUS_PERCENT + 99) / 100
+}
+
+/// A puzzle with fewer answers than this is harder to solve. Used by
+/// `difficulty`.
+pub const DIFFICULTY_FEW_ANSWERS: usize = 20;
+
+/// A puzzle with at most this many pangrams is harder to solve. Used by
+/// `difficulty`.
+pub const DIFFICULTY_FEW_PANGRAMS: usize = 1;
+
+/// A puzzle whose answers average at least this many letters is harder to
+/// solve. Used by `difficulty`.
+pub const DIFFICULTY_LONG_AVERAGE_LEN: usize = 6;
+
+/// How hard a puzzle is to solve, as rated by `difficulty`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    /// Plenty of answers and pangrams, mostly short words.
+    Easy,
+    /// One of the signs of a hard puzzle.
+    Medium,
+    /// Two or more of the signs of a hard puzzle.
+    Hard,
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "easy"),
+            Difficulty::Medium => write!(f, "medium"),
+            Difficulty::Hard => write!(f, "hard"),
+        }
+    }
+}
+
+/// Rate how hard a puzzle is from all of its `answers`. The puzzle gets one
+/// point for each of these that is true:
+///     1. There are fewer than `DIFFICULTY_FEW_ANSWERS` answers.
+///     2. There are at most `DIFFICULTY_FEW_PANGRAMS` pangrams.
+///     3. The answers average at least `DIFFICULTY_LONG_AVERAGE_LEN` letters.
+///
+/// A puzzle with no points is easy, one point is medium and two or more points
+/// is hard. A puzzle without any answers is hard.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{difficulty, find_all, Difficulty};
+/// let answers = find_all(["loon", "pronoun", "unpopular"], 'o', "unrlap");
+///
+/// assert_eq!(Difficulty::Hard, difficulty(&answers));
+/// ```
+pub fn difficulty(answers: &[Answer]) -> Difficulty {
+    let total_len: usize = answers.iter().map(|a| a.word.chars().count()).sum();
+    let pangram_count = answers.iter().filter(|a| a.is_pangram).count();
+
+    let points = [
+        answers.len() < DIFFICULTY_FEW_ANSWERS,
+        pangram_count <= DIFFICULTY_FEW_PANGRAMS,
+        // Compare totals rather than dividing to avoid rounding the average.
+        total_len >= DIFFICULTY_LONG_AVERAGE_LEN * answers.len(),
+    ]
+    .iter()
+    .filter(|&&p| p)
+    .count();
+
+    match points {
+        0 => Difficulty::Easy,
+        1 => Difficulty::Medium,
+        _ => Difficulty::Hard,
+    }
+}
+
+/// Choose the fewest answers needed to raise `current_score` to at least
+/// `target`, by taking the highest scoring answers first. The answers should
+/// be the words that have not been found yet. Answers are returned in the
+/// order they were chosen, and the list is empty when `current_score` already
+/// reaches `target`. If every answer together is not enough to reach `target`
+/// then all of them are returned.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, words_to_reach};
+/// let answers = find_all(["loon", "pronoun", "unpopular"], 'o', "unrlap");
+/// let needed = words_to_reach(&answers, 4, 20);
+///
+/// assert_eq!(vec!["unpopular"], needed.iter().map(|a| a.word.as_str()).collect::<Vec<_>>());
+/// ```
+pub fn words_to_reach(answers: &[Answer], current_score: i32, target: i32) -> Vec<&Answer> {
+    let mut by_score: Vec<&Answer> = answers.iter().collect();
+    by_score.sort();
+
+    let mut score = current_score;
+
+    by_score
+        .into_iter()
+        .take_while(|a| {
+            let needed = score < target;
+            score += a.score;
+            needed
+        })
+        .collect()
+}
+
+/// Get the highest scoring answer in `answers` without sorting them, or `None`
+/// if there are no answers. Ties are broken by the canonical ordering of
+/// answers (see `Answer`).
+///
+/// # Examples
+/// ```
+/// use spellingbee::{best_answer, find_all};
+/// let answers = find_all(["loon", "pronoun", "unpopular"], 'o', "unrlap");
+///
+/// assert_eq!("unpopular", best_answer(&answers).unwrap().word);
+/// ```
+pub fn best_answer(answers: &[Answer]) -> Option<&Answer> {
+    // Answers order from best to worst so the best answer is the "largest" one
+    // in reversed order.
+    answers.iter().max_by(|a, b| b.cmp(a))
+}
+
+/// Sort `answers` from highest to lowest score, drop any answer scoring less
+/// than `min_score`, and then keep only the `top` highest scoring answers.
+/// Answers are sorted by their canonical ordering (see `Answer`) so the answers
+/// kept when there is a tie at the `top` cutoff are always the same.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{filter_answers, find_all};
+/// let answers = find_all(["loon", "pronoun", "unpopular", "roll"], 'o', "unrlap");
+/// let best = filter_answers(answers, Some(2), Some(1));
+///
+/// assert_eq!(1, best.len());
+/// assert_eq!("unpopular", best[0].word);
+/// ```
+pub fn filter_answers(
+    mut answers: Vec<Answer>,
+    min_score: Option<i32>,
+    top: Option<usize>,
+) -> Vec<Answer> {
+    if let Some(min_score) = min_score {
+        answers.retain(|a| a.score >= min_score);
+    }
+
+    answers.sort();
+
+    if let Some(top) = top {
+        answers.truncate(top);
+    }
+
+    answers
+}
+
+/// Remove every answer whose word is listed in `exclude`, for example words
+/// that were already found while playing. Words are compared exactly.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, remove_words};
+/// use std::collections::HashSet;
+///
+/// let answers = find_all(["loon", "pronoun", "roll"], 'o', "unrlap");
+/// let found: HashSet<String> = ["loon".to_string()].into_iter().collect();
+///
+/// assert_eq!(2, remove_words(answers, &found).len());
+/// ```
+#[cfg(feature = "std")]
+pub fn remove_words(mut answers: Vec<Answer>, exclude: &HashSet<String>) -> Vec<Answer> {
+    answers.retain(|a| !exclude.contains(&a.word));
+    answers
+}
+
+/// Get the answers in `current` whose word is not in `previous`, such as the
+/// answers from an earlier run, keeping their order. Words are compared
+/// exactly.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{diff_answers, find_all};
+/// use std::collections::HashSet;
+///
+/// let answers = find_all(["loon", "pronoun", "roll"], 'o', "unrlap");
+/// let previous: HashSet<String> = ["loon".to_string()].into_iter().collect();
+/// let new = diff_answers(&answers, &previous);
+///
+/// assert_eq!(vec!["pronoun", "roll"], new.iter().map(|a| a.word.as_str()).collect::<Vec<_>>());
+/// ```
+#[cfg(feature = "std")]
+pub fn diff_answers<'a>(current: &'a [Answer], previous: &HashSet<String>) -> Vec<&'a Answer> {
+    current
+        .iter()
+        .filter(|a| !previous.contains(&a.word))
+        .collect()
+}
+
+/// Add extra credit points from `bonuses` to every answer whose word is in the
+/// map. The bonus is added on top of the answer's normal score, and entries
+/// that do not match an answer are ignored. Words are compared exactly.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{apply_bonuses, find_all};
+/// use std::collections::HashMap;
+///
+/// let mut answers = find_all(["loon", "pronoun"], 'o', "unrlap");
+/// let bonuses: HashMap<String, i32> = [("loon".to_string(), 5)].into_iter().collect();
+/// apply_bonuses(&mut answers, &bonuses);
+///
+/// assert_eq!(6, answers[0].score);
+/// assert_eq!(7, answers[1].score);
+/// ```
+#[cfg(feature = "std")]
+pub fn apply_bonuses(answers: &mut [Answer], bonuses: &HashMap<String, i32>) {
+    for ans in answers {
+        if let Some(bonus) = bonuses.get(&ans.word) {
+            ans.score += bonus;
+        }
+    }
+}
+
+/// Combine two lists of answers, such as answers from different dictionaries,
+/// so that each word is only listed once. When a word is in both lists the
+/// answer with the higher score is kept, along with its pangram flag, and ties
+/// keep the first answer. Answers are kept in the order their words first
+/// appear in `a` and then `b`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, merge_answers};
+/// let a = find_all(["loon", "pronoun"], 'o', "unrlap");
+/// let b = find_all(["pronoun", "unpopular"], 'o', "unrlap");
+///
+/// assert_eq!(3, merge_answers(a, b).len());
+/// ```
+pub fn merge_answers(a: Vec<Answer>, b: Vec<Answer>) -> Vec<Answer> {
+    let mut positions: BTreeMap<String, usize> = BTreeMap::new();
+    let mut merged: Vec<Answer> = Vec::with_capacity(a.len() + b.len());
+
+    for ans in a.into_iter().chain(b) {
+        match positions.get(&ans.word) {
+            Some(&i) => {
+                if ans.score > merged[i].score {
+                    merged[i] = ans;
+                }
+            }
+            None => {
+                positions.insert(ans.word.clone(), merged.len());
+                merged.push(ans);
+            }
+        }
+    }
+
+    merged
+}
+
+/// Keys that a list of answers can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    /// Highest score first, using the canonical `Answer` ordering.
+    #[default]
+    Score,
+    /// Alphabetically by word.
+    Alpha,
+    /// Shortest word first, with words of the same length sorted
+    /// alphabetically.
+    Length,
+}
+
+impl SortKey {
+    /// Compare two answers using this sort key. Answers that are equal under
+    /// the key fall back to the canonical `Answer` ordering so the result is
+    /// always deterministic.
+    pub fn compare(&self, a: &Answer, b: &Answer) -> Ordering {
+        match self {
+            SortKey::Score => a.cmp(b),
+            SortKey::Alpha => a.word.cmp(&b.word).then_with(|| a.cmp(b)),
+            SortKey::Length => a
+                .word
+                .chars()
+                .count()
+                .cmp(&b.word.chars().count())
+                .then_with(|| a.word.cmp(&b.word))
+                .then_with(|| a.cmp(b)),
+        }
+    }
+}
+
+/// Sort `answers` using the given sort key.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, sort_answers_by, SortKey};
+/// let mut answers = find_all(["pronoun", "roll", "loon"], 'o', "unrlap");
+/// sort_answers_by(&mut answers, SortKey::Alpha);
+///
+/// assert_eq!("loon", answers[0].word);
+/// ```
+pub fn sort_answers_by(answers: &mut [Answer], key: SortKey) {
+    answers.sort_by(|a, b| key.compare(a, b));
+}
+
+/// How to order answers that are equal under the primary sort key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Alphabetically by word.
+    #[default]
+    Alpha,
+    /// Longest word first.
+    Length,
+    /// Pangrams before other answers.
+    Pangram,
+}
+
+impl TieBreak {
+    /// Compare two answers using only this tie-break.
+    pub fn compare(&self, a: &Answer, b: &Answer) -> Ordering {
+        match self {
+            TieBreak::Alpha => a.word.cmp(&b.word),
+            TieBreak::Length => b.word.chars().count().cmp(&a.word.chars().count()),
+            TieBreak::Pangram => b.is_pangram.cmp(&a.is_pangram),
+        }
+    }
+}
+
+/// Options for `sort_answers` that choose the primary sort key and how ties
+/// are broken independently of each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SortOptions {
+    /// Key answers are sorted by first. Unlike `SortKey::compare` only the key
+    /// itself is compared: the highest score, the word or the shortest word.
+    pub primary: SortKey,
+    /// Order of answers that are equal under the primary key.
+    pub tie_break: TieBreak,
+}
+
+/// Sort `answers` by the primary key in `options` and then its tie-break.
+/// The sort is stable, so answers that are still equal keep their order.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, sort_answers, SortKey, SortOptions, TieBreak};
+/// let mut answers = find_all(["roll", "loon", "pronoun", "upon"], 'o', "unrlap");
+/// let options = SortOptions {
+///     primary: SortKey::Length,
+///     tie_break: TieBreak::Alpha,
+/// };
+/// sort_answers(&mut answers, &options);
+///
+/// assert_eq!("loon", answers[0].word);
+/// assert_eq!("pronoun", answers[3].word);
+/// ```
+pub fn sort_answers(answers: &mut [Answer], options: &SortOptions) {
+    answers.sort_by(|a, b| {
+        let primary = match options.primary {
+            SortKey::Score => b.score.cmp(&a.score),
+            SortKey::Alpha => a.word.cmp(&b.word),
+            SortKey::Length => a.word.chars().count().cmp(&b.word.chars().count()),
+        };
+
+        primary.then_with(|| options.tie_break.compare(a, b))
+    });
+}
+
+/// Count how many answers there are of each length in letters. This is a
+/// cheaper alternative to `group_by_length` when only the counts are needed.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, length_histogram};
+/// let answers = find_all(["roll", "loon", "pronoun"], 'o', "unrlap");
+/// let histogram = length_histogram(&answers);
+///
+/// assert_eq!(2, histogram[&4]);
+/// assert_eq!(1, histogram[&7]);
+/// ```
+pub fn length_histogram(answers: &[Answer]) -> BTreeMap<usize, usize> {
+    let mut histogram = BTreeMap::new();
+
+    for ans in answers {
+        *histogram.entry(ans.word.chars().count()).or_insert(0) += 1;
+    }
+
+    histogram
+}
+
+/// Group answers by their length in letters. Groups are ordered from shortest
+/// to longest, and the answers in each group are sorted alphabetically.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, group_by_length};
+/// let answers = find_all(["roll", "loon", "pronoun"], 'o', "unrlap");
+/// let groups = group_by_length(&answers);
+///
+/// assert_eq!(vec![4, 7], groups.keys().copied().collect::<Vec<_>>());
+/// assert_eq!("loon", groups[&4][0].word);
+/// ```
+pub fn group_by_length(answers: &[Answer]) -> BTreeMap<usize, Vec<&Answer>> {
+    let mut groups: BTreeMap<usize, Vec<&Answer>> = BTreeMap::new();
+
+    for ans in answers {
+        groups
+            .entry(ans.word.chars().count())
+            .or_default()
+            .push(ans);
+    }
+
+    for group in groups.values_mut() {
+        group.sort_by(|a, b| a.word.cmp(&b.word));
+    }
+
+    groups
+}
+
+/// Count how many answers start with each two letter prefix, like the two
+/// letter list in the NYT spelling bee hints. Prefixes are lowercased so words
+/// that only differ by case are counted together.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, two_letter_counts};
+/// let answers = find_all(["loon", "loop", "pronoun"], 'o', "unrlap");
+/// let counts = two_letter_counts(&answers);
+///
+/// assert_eq!(Some(&2), counts.get("lo"));
+/// assert_eq!(Some(&1), counts.get("pr"));
+/// ```
+pub fn two_letter_counts(answers: &[Answer]) -> BTreeMap<String, usize> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+
+    for ans in answers {
+        let prefix: String = ans
+            .word
+            .chars()
+            .take(2)
+            .flat_map(char::to_lowercase)
+            .collect();
+        *counts.entry(prefix).or_default() += 1;
+    }
+
+    counts
+}
+
+/// Group answers that are anagrams of each other, like "mote" and "tome".
+/// Letter case is ignored when comparing letters. Groups are ordered by where
+/// their first answer appears in `answers`, and answers without any anagrams
+/// are in a group by themselves.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{find_all, group_anagrams};
+/// let answers = find_all(["loop", "pool", "polo", "roll"], 'o', "unrlap");
+/// let groups = group_anagrams(&answers);
+///
+/// assert_eq!(2, groups.len());
+/// assert_eq!(3, groups[0].len());
+/// assert_eq!("roll", groups[1][0].word);
+/// ```
+pub fn group_anagrams(answers: &[Answer]) -> Vec<Vec<&Answer>> {
+    let mut group_index: BTreeMap<Vec<char>, usize> = BTreeMap::new();
+    let mut groups: Vec<Vec<&Answer>> = Vec::new();
+
+    for ans in answers {
+        let mut signature: Vec<char> = ans.word.chars().map(fold_case).collect();
+        signature.sort_unstable();
+
+        let index = *group_index.entry(signature).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+
+        groups[index].push(ans);
+    }
+
+    groups
+}
+
+/// Rules used to decide which words are long enough to be answers, and how many
+/// points each answer is worth. `Scoring::default()` matches the rules of the
+/// NYT spelling bee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scoring {
+    /// Points added to the score of a pangram.
+    pub pangram_bonus_mode: PangramBonus,
+    /// Minimum number of letters a word needs to be an answer.
+    pub min_word_len: usize,
+    /// Maximum number of letters an answer can have, or `None` for no limit.
+    pub max_word_len: Option<usize>,
+    /// Words with at least this many letters score their length in points.
+    pub long_word_min_len: usize,
+    /// Points given to answers shorter than `long_word_min_len`.
+    pub short_word_score: i32,
+    /// Minimum number of different letters a word needs to be an answer, or
+    /// zero to accept words no matter how many times they repeat a letter.
+    pub min_distinct_letters: usize,
+    /// Minimum number of times the required letter must appear in an answer.
+    /// Values less than one behave the same as one.
+    pub min_required_occurrences: usize,
+    /// Only accept pangrams as answers. Other words are rejected before they
+    /// are scored.
+    pub pangrams_only: bool,
+    /// Extra points for using a letter, such as rare letters like `q` or `z`.
+    /// Each different letter in a word adds its bonus once, and letters are
+    /// matched exactly. Empty by default.
+    pub letter_bonus: BTreeMap<char, i32>,
+    /// Most points a single answer can be worth, including its pangram and
+    /// letter bonuses, or `None` for no limit.
+    pub max_word_score: Option<i32>,
+}
+
+impl Default for Scoring {
+    fn default() -> Self {
+        Scoring {
+            pangram_bonus_mode: PangramBonus::Flat(PANGRAM_BONUS),
+            min_word_len: MIN_WORD_LENGTH,
+            max_word_len: None,
+            long_word_min_len: LONG_WORD_MIN_LENGTH,
+            short_word_score: 1,
+            min_distinct_letters: 0,
+            min_required_occurrences: 1,
+            pangrams_only: false,
+            letter_bonus: BTreeMap::new(),
+            max_word_score: None,
+        }
+    }
+}
+
+impl Scoring {
+    /// Test if a word of `word_len` letters is long enough, but not too long, to
+    /// be an answer.
+    fn allows_length(&self, word_len: usize) -> bool {
+        word_len >= self.min_word_len && self.max_word_len.is_none_or(|m| word_len <= m)
+    }
+}
+
+/// How many extra points a pangram is worth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PangramBonus {
+    /// Every pangram gets the same number of extra points.
+    Flat(i32),
+    /// Pangrams get this many extra points for each different letter in the
+    /// pangram, which is the number of letters in the puzzle.
+    PerLetter(i32),
+}
+
+impl PangramBonus {
+    /// Get the bonus for a pangram using `letter_count` different letters.
+    fn points(&self, letter_count: usize) -> i32 {
+        match *self {
+            PangramBonus::Flat(points) => points,
+            PangramBonus::PerLetter(points) => points * letter_count as i32,
+        }
+    }
+}
+
+/// Describes why a set of puzzle letters is not a valid spelling bee puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// A puzzle letter is not an alphabetic character.
+    NotAlphabetic(char),
+    /// The required character is also listed in the extra characters.
+    RequiredInExtra(char),
+    /// A character is listed more than once in the extra characters.
+    DuplicateExtra(char),
+    /// No extra characters were given.
+    EmptyExtra,
+    /// A combined puzzle string did not have exactly seven letters. Holds the
+    /// number of letters that were given.
+    WrongLetterCount(usize),
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleError::NotAlphabetic(c) => write!(f, "'{}' is not a letter", c.escape_debug()),
+            PuzzleError::RequiredInExtra(c) => {
+                write!(f, "required letter '{}' is also an extra letter", c)
+            }
+            PuzzleError::DuplicateExtra(c) => {
+                write!(f, "extra letter '{}' is listed more than once", c)
+            }
+            PuzzleError::EmptyExtra => write!(f, "no extra letters were given"),
+            PuzzleError::WrongLetterCount(n) => write!(
+                f,
+                "expected {} puzzle letters but {} were given",
+                PUZZLE_LETTER_COUNT, n
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PuzzleError {}
+
+/// Errors returned when finding answers in a dictionary.
+#[derive(Debug)]
+pub enum Error {
+    /// The dictionary could not be read.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    /// The puzzle letters are not valid.
+    Puzzle(PuzzleError),
+    /// The line with this (1-based) line number could not be decoded.
+    Decode(usize, String),
+    /// Answers or a saved `DictionaryIndex` could not be parsed from JSON.
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Puzzle(err) => write!(f, "invalid puzzle: {}", err),
+            Error::Decode(line, message) => write!(f, "line {}: {}", line, message),
+            #[cfg(feature = "serde")]
+            Error::Json(err) => write!(f, "invalid JSON: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Puzzle(err) => Some(err),
+            Error::Decode(..) => None,
+            #[cfg(feature = "serde")]
+            Error::Json(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+impl From<PuzzleError> for Error {
+    fn from(err: PuzzleError) -> Self {
+        Error::Puzzle(err)
+    }
+}
+
+/// The letters of a spelling bee puzzle: a required letter that must appear in
+/// every answer, and the extra letters that answers may also use.
+///
+/// Puzzles are validated once when they are created, and the letters are
+/// prepared so that checking words against the puzzle is fast.
+///
+/// # Examples
+/// ```
+/// use spellingbee::Puzzle;
+/// let puzzle = Puzzle::new('o', "unrlap").unwrap();
+///
+/// assert!(puzzle.check_word("unpopular").unwrap().is_pangram);
+/// assert_eq!(2, puzzle.find_all(["loon", "foobar", "pronoun"]).len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    letters: LetterSet,
+}
+
+impl Puzzle {
+    /// Create a new puzzle, returning an error if `required` and `extra` are
+    /// not a valid set of puzzle letters. See `validate_puzzle` for the rules
+    /// that are checked.
+    pub fn new(required: char, extra: &str) -> Result<Puzzle, PuzzleError> {
+        validate_puzzle(required, extra)?;
+        Ok(Puzzle::new_unchecked(required, extra))
+    }
+
+    /// Create a puzzle without validating its letters. This is used by the free
+    /// functions like `find_all` which have always accepted any letters.
+    fn new_unchecked(required: char, extra: &str) -> Puzzle {
+        Puzzle {
+            letters: LetterSet::new(required, extra),
+        }
+    }
+
+    /// The letter that must appear in every answer.
+    pub fn required(&self) -> char {
+        self.letters.required()
+    }
+
+    /// The other letters allowed to appear in an answer.
+    pub fn extra(&self) -> &str {
+        self.letters.extra()
+    }
+
+    /// The prepared set of letters used by this puzzle.
+    pub fn letters(&self) -> &LetterSet {
+        &self.letters
+    }
+
+    /// Test if `word` is an answer to this puzzle. See the free `check_word`
+    /// function for a description of the rules.
+    pub fn check_word(&self, word: &str) -> Option<Answer> {
+        self.check_word_with(word, &Scoring::default())
+    }
+
+    /// Test if `word` is an answer to this puzzle using the rules in `scoring`.
+    pub fn check_word_with(&self, word: &str, scoring: &Scoring) -> Option<Answer> {
+        check_word_set_with(word, &self.letters, scoring)
+    }
+
+    /// Test if a word from a `DictionaryIndex` is an answer to this puzzle,
+    /// using the word's precomputed letter mask when possible.
+    fn check_entry(&self, entry: &IndexEntry, scoring: &Scoring) -> Option<Answer> {
+        let letters = &self.letters;
+
+        match (&letters.masks, entry.mask) {
+            (Some(masks), Some(word_mask)) => check_mask(
+                &entry.word,
+                word_mask,
+                entry.len,
+                letters.required,
+                masks,
+                scoring,
+            ),
+            _ => check_word_with(&entry.word, letters.required, &letters.extra, scoring),
+        }
+    }
+
+    /// Finds all answers to this puzzle from an iterable list of words.
+    pub fn find_all<I, S>(&self, words: I) -> Vec<Answer>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        words
+            .into_iter()
+            .filter_map(|w| self.check_word(w.as_ref()))
+            .collect()
+    }
+}
+
+/// A list of dictionary words prepared ahead of time so it can be searched for
+/// the answers to many puzzles quickly. Each word's letter mask and length are
+/// computed once when the index is built, rather than every time the word is
+/// checked against a puzzle.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{DictionaryIndex, Puzzle};
+/// let index = DictionaryIndex::new(["loon", "foobar", "pronoun", "motel"]);
+///
+/// assert_eq!(2, index.solve(&Puzzle::new('o', "unrlap").unwrap()).len());
+/// assert_eq!(1, index.find_all('t', "elom").len());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DictionaryIndex {
+    entries: Vec<IndexEntry>,
+}
+
+/// A word in a `DictionaryIndex`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct IndexEntry {
+    word: String,
+    /// Letter mask of the word, or `None` if the word has characters other than
+    /// ASCII letters.
+    mask: Option<u64>,
+    /// Length of the word in characters.
+    len: usize,
+}
+
+impl DictionaryIndex {
+    /// Build an index of every word in `words`.
+    pub fn new<I, S>(words: I) -> DictionaryIndex
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let entries = words
+            .into_iter()
+            .map(|w| {
+                let word = trim_word(w.as_ref());
+
+                IndexEntry {
+                    word: word.to_string(),
+                    mask: letter_mask(word),
+                    len: word.chars().count(),
+                }
+            })
+            .collect();
+
+        DictionaryIndex { entries }
+    }
+
+    /// Number of words in the index.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Test if the index has no words.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over every word in the index.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.word.as_str())
+    }
+
+    /// Finds all answers to `puzzle` in the index.
+    pub fn solve(&self, puzzle: &Puzzle) -> Vec<Answer> {
+        self.solve_with(puzzle, &Scoring::default())
+    }
+
+    /// Version of `solve` that uses the rules in `scoring` to accept and score
+    /// words.
+    pub fn solve_with(&self, puzzle: &Puzzle, scoring: &Scoring) -> Vec<Answer> {
+        self.entries
+            .iter()
+            .filter_map(|e| puzzle.check_entry(e, scoring))
+            .collect()
+    }
+
+    /// Finds all answers in the index, the same as the free `find_all`
+    /// function.
+    pub fn find_all(&self, required: char, extra: &str) -> Vec<Answer> {
+        self.solve(&Puzzle::new_unchecked(required, extra))
+    }
+
+    /// Save the index to the file at `path` so it can be loaded again with
+    /// `load` instead of rebuilding it. The modification time of the `source`
+    /// dictionary the index was built from is saved along with it.
+    #[cfg(feature = "serde")]
+    pub fn save(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        source: impl AsRef<std::path::Path>,
+    ) -> Result<(), Error> {
+        use std::io::Write;
+
+        let saved = SavedIndex {
+            source_modified: modified_time(source.as_ref())?,
+            entries: alloc::borrow::Cow::Borrowed(&self.entries),
+        };
+
+        let mut file = io::BufWriter::new(std::fs::File::create(path)?);
+        serde_json::to_writer(&mut file, &saved)?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    /// Load an index saved by `save`. Returns `None` when the index is stale
+    /// because the `source` dictionary was modified after the index was
+    /// saved, in which case the index should be rebuilt.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use spellingbee::{read_dict, DictionaryIndex};
+    /// use std::{fs::File, io::BufReader};
+    ///
+    /// let source = "/usr/share/dict/words";
+    /// let index = match DictionaryIndex::load("words.index", source).unwrap() {
+    ///     Some(index) => index,
+    ///     None => {
+    ///         let index = DictionaryIndex::new(read_dict(BufReader::new(File::open(source)?))?);
+    ///         index.save("words.index", source)?;
+    ///         index
+    ///     }
+    /// };
+    /// # Ok::<(), spellingbee::Error>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn load(
+        path: impl AsRef<std::path::Path>,
+        source: impl AsRef<std::path::Path>,
+    ) -> Result<Option<DictionaryIndex>, Error> {
+        let file = io::BufReader::new(std::fs::File::open(path)?);
+        let saved: SavedIndex = serde_json::from_reader(file)?;
+
+        if saved.source_modified != modified_time(source.as_ref())? {
+            return Ok(None);
+        }
+
+        Ok(Some(DictionaryIndex {
+            entries: saved.entries.into_owned(),
+        }))
+    }
+}
+
+/// A `DictionaryIndex` as it is written to disk by `DictionaryIndex::save`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedIndex<'a> {
+    /// Modification time of the dictionary the index was built from, as
+    /// seconds and nanoseconds since the unix epoch.
+    source_modified: (u64, u32),
+    entries: alloc::borrow::Cow<'a, [IndexEntry]>,
+}
+
+/// Get the modification time of the file at `path` as seconds and nanoseconds
+/// since the unix epoch. Times before the epoch are treated as the epoch.
+#[cfg(feature = "serde")]
+fn modified_time(path: &std::path::Path) -> Result<(u64, u32), Error> {
+    let modified = std::fs::metadata(path)?.modified()?;
+    let since_epoch = modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+
+    Ok((since_epoch.as_secs(), since_epoch.subsec_nanos()))
+}
+
+impl<S: AsRef<str>> FromIterator<S> for DictionaryIndex {
+    fn from_iter<I: IntoIterator<Item = S>>(words: I) -> Self {
+        DictionaryIndex::new(words)
+    }
+}
+
+/// Finds all spelling bee answers from an iterable list of words.
+pub fn find_all<I, S>(words: I, required: char, extra: &str) -> Vec<Answer>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    // IntoIterator inspiration from: https://stackoverflow.com/a/35626785
+    let mut answers = Vec::new();
+    for_each_answer(words, required, extra, |ans| answers.push(ans));
+    answers
+}
+
+/// Call `f` with each spelling bee answer from an iterable list of words, in
+/// the same order as `find_all`, without collecting the answers.
+///
+/// # Examples
+/// ```
+/// use spellingbee::for_each_answer;
+/// let mut total = 0;
+/// for_each_answer(["loon", "pronoun", "foobar"], 'o', "unrlap", |ans| total += ans.score);
+///
+/// assert_eq!(8, total);
+/// ```
+pub fn for_each_answer<I, S, F>(words: I, required: char, extra: &str, mut f: F)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+    F: FnMut(Answer),
+{
+    // Prepare the puzzle letters once up front rather than for every word.
+    let letters = LetterSet::new(required, extra);
+
+    for word in words {
+        if let Some(ans) = check_word_set(word.as_ref(), &letters) {
+            f(ans);
+        }
+    }
+}
+
+/// Finds all spelling bee answers from an iterable list of fallible words, such
+/// as the lines of a `BufRead`. Checking stops at the first error, which is
+/// returned instead of the answers.
+///
+/// # Examples
+/// ```
+/// use spellingbee::find_all_results;
+/// let words: [Result<&str, &str>; 3] = [Ok("loon"), Err("bad line"), Ok("pronoun")];
+///
+/// assert_eq!(Err("bad line"), find_all_results(words, 'o', "unrlap"));
+/// ```
+pub fn find_all_results<I, S, E>(words: I, required: char, extra: &str) -> Result<Vec<Answer>, E>
+where
+    I: IntoIterator<Item = Result<S, E>>,
+    S: AsRef<str>,
+{
+    let letters = LetterSet::new(required, extra);
+    let mut answers = Vec::new();
+
+    for word in words {
+        if let Some(ans) = check_word_set(word?.as_ref(), &letters) {
+            answers.push(ans);
+        }
+    }
+
+    Ok(answers)
+}
+
+/// Finds all spelling bee answers from an iterable list of words, sorted by
+/// their canonical ordering: highest score first, then pangrams before other
+/// answers, and then alphabetically.
+///
+/// # Examples
+/// ```
+/// use spellingbee::find_all_sorted;
+/// let answers = find_all_sorted(["loon", "pronoun", "unpopular"], 'o', "unrlap");
+///
+/// assert_eq!("unpopular", answers[0].word);
+/// assert_eq!("loon", answers[2].word);
+/// ```
+pub fn find_all_sorted<I, S>(words: I, required: char, extra: &str) -> Vec<Answer>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut answers = find_all(words, required, extra);
+    answers.sort();
+    answers
+}
+
+/// Finds the `n` best spelling bee answers from an iterable list of words,
+/// sorted by their canonical ordering the same as `find_all_sorted`. Only the
+/// best `n` answers seen so far are kept while the words are checked, so the
+/// other answers are never stored or sorted.
+///
+/// # Examples
+/// ```
+/// use spellingbee::top_answers;
+/// let answers = top_answers(["loon", "pronoun", "unpopular"], 'o', "unrlap", 2);
+///
+/// assert_eq!(2, answers.len());
+/// assert_eq!("unpopular", answers[0].word);
+/// assert_eq!("pronoun", answers[1].word);
+/// ```
+pub fn top_answers<I, S>(words: I, required: char, extra: &str, n: usize) -> Vec<Answer>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // Answers order best first, so the top of this max heap is the worst
+    // answer kept and is the one dropped when a better answer comes along.
+    let mut best: BinaryHeap<Answer> = BinaryHeap::with_capacity(n + 1);
+
+    for_each_answer(words, required, extra, |ans| {
+        best.push(ans);
+
+        if best.len() > n {
+            best.pop();
+        }
+    });
+
+    best.into_sorted_vec()
+}
+
+/// Finds all spelling bee answers from an iterable list of words by building a
+/// `DictionaryIndex` and solving the puzzle through it. The answers are the
+/// same as `find_all`. The index is returned with the answers so it can be kept
+/// and reused for other puzzles, which is where it saves time.
+///
+/// # Examples
+/// ```
+/// use spellingbee::find_all_indexed;
+/// let (answers, index) = find_all_indexed(["loon", "foobar", "pronoun", "motel"], 'o', "unrlap");
+///
+/// assert_eq!(2, answers.len());
+/// assert_eq!(1, index.find_all('t', "elom").len());
+/// ```
+pub fn find_all_indexed<I, S>(
+    words: I,
+    required: char,
+    extra: &str,
+) -> (Vec<Answer>, DictionaryIndex)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let index = DictionaryIndex::new(words);
+    (index.find_all(required, extra), index)
+}
+
+/// Finds all spelling bee answers from a slice of words, such as a `&[&str]`
+/// or a `&[String]`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::find_all_slice;
+/// let words = vec!["loon".to_string(), "pronoun".to_string()];
+///
+/// assert_eq!(2, find_all_slice(&words, 'o', "unrlap").len());
+/// ```
+pub fn find_all_slice(words: &[impl AsRef<str>], required: char, extra: &str) -> Vec<Answer> {
+    find_all(words, required, extra)
+}
+
+/// Finds the answers for every possible required letter of a puzzle. Each
+/// letter in `letters` takes a turn as the required letter with the others as
+/// the extra letters, and the answers are returned by required letter. The
+/// words are only read once no matter how many letters there are.
+///
+/// # Examples
+/// ```
+/// use spellingbee::solve_all_centers;
+/// let answers = solve_all_centers(["loon", "pull", "unpopular"], "ounrlap");
+///
+/// assert_eq!(2, answers[&'o'].len());
+/// assert_eq!(2, answers[&'u'].len());
+/// assert_eq!(1, answers[&'r'].len());
+/// ```
+pub fn solve_all_centers<I, S>(words: I, letters: &str) -> BTreeMap<char, Vec<Answer>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let centers: Vec<(char, LetterSet)> = letters
+        .chars()
+        .collect::<BTreeSet<char>>()
+        .into_iter()
+        .map(|c| {
+            let extra: String = letters.chars().filter(|&e| e != c).collect();
+            (c, LetterSet::new(c, &extra))
+        })
+        .collect();
+
+    let mut answers: BTreeMap<char, Vec<Answer>> =
+        centers.iter().map(|&(c, _)| (c, Vec::new())).collect();
+
+    for word in words {
+        for (c, set) in &centers {
+            if let Some(ans) = check_word_set(word.as_ref(), set) {
+                answers.entry(*c).or_default().push(ans);
+            }
+        }
+    }
+
+    answers
+}
+
+/// Lazily finds spelling bee answers from an iterable list of words. Words are
+/// only read from `words` as answers are pulled from the returned iterator,
+/// which lets callers stream, count or take the first few answers without
+/// checking the entire word list.
+///
+/// # Examples
+/// ```
+/// use spellingbee::find_all_iter;
+/// let words = ["loon", "foobar", "pronoun", "unpopular"];
+/// let first = find_all_iter(words, 'o', "unrlap").take(2);
+/// assert_eq!(vec!["loon", "pronoun"], first.map(|a| a.word).collect::<Vec<_>>());
+/// ```
+pub fn find_all_iter<'a, I, S>(
+    words: I,
+    required: char,
+    extra: &'a str,
+) -> impl Iterator<Item = Answer> + 'a
+where
+    I: IntoIterator<Item = S>,
+    I::IntoIter: 'a,
+    S: AsRef<str>,
+{
+    // Prepare the puzzle letters once up front rather than for every word.
+    let letters = LetterSet::new(required, extra);
+
+    words
+        .into_iter()
+        .filter_map(move |w| check_word_set(w.as_ref(), &letters))
+}
+
+/// Get every word in `words` that uses exactly as many different letters as
+/// a standard puzzle has, so the word is a pangram of the puzzle made from its
+/// letters. Letter case is ignored when counting letters, entries with
+/// non-letter characters are skipped, and the words are returned in
+/// dictionary order.
+///
+/// # Examples
+/// ```
+/// use spellingbee::find_pangram_candidates;
+/// let words: Vec<String> = ["unpopular", "loon", "pull"].iter().map(|w| w.to_string()).collect();
+///
+/// assert_eq!(vec!["unpopular"], find_pangram_candidates(&words));
+/// ```
+pub fn find_pangram_candidates(words: &[String]) -> Vec<String> {
+    words
+        .iter()
+        .filter(|w| is_candidate_word(w))
+        .filter(|w| distinct_letters(w).len() == PUZZLE_LETTER_COUNT)
+        .cloned()
+        .collect()
+}
+
+/// Get the different letters in `word` after lowercasing them.
+fn distinct_letters(word: &str) -> BTreeSet<char> {
+    word.chars().map(fold_case).collect()
+}
+
+/// Count the answers to every standard seven letter puzzle that has a pangram in
+/// `words`. Each word with exactly seven different letters gives one set of
+/// puzzle letters, and each of those letters takes a turn as the required
+/// letter. Puzzles are written with the required letter first followed by the
+/// other letters in sorted order, the same as `parse_puzzle` expects, and are
+/// returned with the most answers first.
+///
+/// # Examples
+/// ```
+/// use spellingbee::puzzle_answer_counts;
+/// let words: Vec<String> = ["unpopular", "loon", "pull"].iter().map(|w| w.to_string()).collect();
+/// let counts = puzzle_answer_counts(&words);
+///
+/// assert_eq!(7, counts.len());
+/// assert_eq!(("lanopru".to_string(), 3), counts[0]);
+/// ```
+pub fn puzzle_answer_counts(words: &[String]) -> Vec<(String, usize)> {
+    let letter_sets: BTreeSet<String> = find_pangram_candidates(words)
+        .iter()
+        .map(|w| distinct_letters(w).into_iter().collect())
+        .collect();
+
+    let mut counts: Vec<(String, usize)> = Vec::new();
+
+    for letters in &letter_sets {
+        for (required, answers) in solve_all_centers(words, letters) {
+            let puzzle: String = core::iter::once(required)
+                .chain(letters.chars().filter(|&c| c != required))
+                .collect();
+            counts.push((puzzle, answers.len()));
+        }
+    }
+
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Finds all spelling bee answers from a list of words, checking the words in
+/// parallel across all available cores. The answers are the same as those
+/// returned by `find_all` but they are not guaranteed to be in the same order.
+#[cfg(feature = "rayon")]
+pub fn find_all_par<I, S>(words: I, required: char, extra: &str) -> Vec<Answer>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str> + Send + Sync,
+{
+    use rayon::prelude::*;
+
+    let words: Vec<S> = words.into_iter().collect();
+    let puzzle = Puzzle::new_unchecked(required, extra);
+
+    words
+        .par_iter()
+        .filter_map(|w| puzzle.check_word(w.as_ref()))
+        .collect()
+}
+
+/// Finds all spelling bee answers from an iterable list of words, skipping any
+/// word that was already returned as an answer. Words are compared exactly so
+/// "Tote" and "tote" are considered different words.
+pub fn find_all_unique<I, S>(words: I, required: char, extra: &str) -> Vec<Answer>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut seen: BTreeSet<String> = BTreeSet::new();
+    let mut answers: Vec<Answer> = Vec::new();
+
+    for ans in find_all_iter(words, required, extra) {
+        if seen.insert(ans.word.clone()) {
+            answers.push(ans);
+        }
+    }
+
+    answers
+}
+
+/// Finds all spelling bee answers from an iterable list of words, ignoring
+/// letter case when comparing words to the puzzle letters. See `check_word_ci`
+/// for details.
+pub fn find_all_ci<I, S>(words: I, required: char, extra: &str) -> Vec<Answer>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let check = check_word_ci_fn(required, extra);
+
+    words
+        .into_iter()
+        .filter_map(|w| check(w.as_ref()))
+        .collect()
+}
+
+/// Find all valid answers from a dictionary reader that yields one word per
+/// line. Any whitespace surrounding a word is removed before it is checked,
+/// which also takes care of the trailing `\r` left behind by CRLF line endings.
+/// Entries with non-letter characters are skipped without being checked.
+/// Letter case is ignored when `ignore_case` is true.
+///
+/// Reading stops at the first line that cannot be read, and the error is
+/// returned. Lines that are not valid UTF-8 are reported as `Error::Decode`
+/// along with their line number.
+///
+/// # Examples
+/// ```
+/// use spellingbee::find_all_with_dict;
+/// let dict = "loon\r\nfoobar\r\npronoun\r\n".as_bytes();
+///
+/// assert_eq!(2, find_all_with_dict(dict, 'o', "unrlap", false).unwrap().len());
+/// ```
+#[cfg(feature = "std")]
+pub fn find_all_with_dict<R: BufRead>(
+    dict: R,
+    required: char,
+    extra: &str,
+    ignore_case: bool,
+) -> Result<Vec<Answer>, Error> {
+    let words = dict_words(dict);
+
+    if ignore_case {
+        let check = check_word_ci_fn(required, extra);
+
+        words
+            .filter_map(|word| word.map(|w| check(&w)).transpose())
+            .collect()
+    } else {
+        find_all_results(words, required, extra)
+    }
+}
+
+/// Read every word from a dictionary reader that yields one word per line, so
+/// the same dictionary can be searched for answers to many puzzles. Words are
+/// trimmed and entries with non-letter characters are skipped, the same as in
+/// `find_all_with_dict`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::read_dict;
+/// let words = read_dict(" loon\r\ncan't\npronoun\n".as_bytes()).unwrap();
+///
+/// assert_eq!(vec!["loon", "pronoun"], words);
+/// ```
+#[cfg(feature = "std")]
+pub fn read_dict<R: BufRead>(dict: R) -> Result<Vec<String>, Error> {
+    dict_words(dict).collect()
+}
+
+/// Lazily read the candidate words from a dictionary reader. A line that
+/// cannot be read is yielded as an error, and lines that are not valid UTF-8
+/// are reported as `Error::Decode` along with their line number. A byte order
+/// mark at the start of the dictionary is removed.
+#[cfg(feature = "std")]
+fn dict_words<R: BufRead>(dict: R) -> impl Iterator<Item = Result<String, Error>> {
+    dict.lines()
+        .enumerate()
+        .map(|(index, maybe_line)| match maybe_line {
+            Ok(line) if index == 0 => Ok(trim_line(strip_bom(line))),
+            Ok(line) => Ok(trim_line(line)),
+            Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                Err(Error::Decode(index + 1, err.to_string()))
+            }
+            Err(err) => Err(Error::Io(err)),
+        })
+        .filter(|word| word.as_ref().map_or(true, |w| is_candidate_word(w)))
+}
+
+/// Statistics about the contents of a dictionary, returned by
+/// `dictionary_stats`.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DictStats {
+    /// Number of lines read from the dictionary.
+    pub lines: usize,
+    /// Number of lines that were empty or only whitespace.
+    pub blank_lines: usize,
+    /// Line numbers, starting at one, of lines that are not valid UTF-8.
+    pub invalid_utf8_lines: Vec<usize>,
+    /// Number of entries with characters other than letters, like "can't".
+    /// These entries can never be answers.
+    pub non_letter_words: usize,
+    /// The shortest entry made up only of letters. The first one wins ties.
+    pub shortest: Option<String>,
+    /// The longest entry made up only of letters. The first one wins ties.
+    pub longest: Option<String>,
+}
+
+/// Scan a dictionary with one word per line and collect statistics about it
+/// without solving a puzzle. Whitespace surrounding each line is ignored, and
+/// word lengths are measured in unicode scalar values. Lines that are not
+/// valid UTF-8 are recorded rather than stopping the scan, but any other I/O
+/// error stops the scan and is returned.
+///
+/// # Examples
+/// ```
+/// use spellingbee::dictionary_stats;
+/// let stats = dictionary_stats("loon\ncan't\npronoun\n".as_bytes()).unwrap();
+///
+/// assert_eq!(3, stats.lines);
+/// assert_eq!(1, stats.non_letter_words);
+/// assert_eq!(Some("pronoun".to_string()), stats.longest);
+/// ```
+#[cfg(feature = "std")]
+pub fn dictionary_stats(reader: impl BufRead) -> io::Result<DictStats> {
+    let mut stats = DictStats::default();
+
+    for bytes in reader.split(b'\n') {
+        let bytes = bytes?;
+        stats.lines += 1;
+
+        let line = match String::from_utf8(bytes) {
+            Ok(line) if stats.lines == 1 => trim_line(strip_bom(line)),
+            Ok(line) => trim_line(line),
+            Err(_) => {
+                stats.invalid_utf8_lines.push(stats.lines);
+                continue;
+            }
+        };
+
+        if line.is_empty() {
+            stats.blank_lines += 1;
+        } else if !is_candidate_word(&line) {
+            stats.non_letter_words += 1;
+        } else {
+            let len = line.chars().count();
+
+            if stats
+                .shortest
+                .as_ref()
+                .is_none_or(|w| len < w.chars().count())
+            {
+                stats.shortest = Some(line.clone());
+            }
+
+            if stats
+                .longest
+                .as_ref()
+                .is_none_or(|w| len > w.chars().count())
+            {
+                stats.longest = Some(line);
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Find all valid answers in an in-memory dictionary with one word per line.
+/// Whitespace surrounding each word, including the `\r` from CRLF line
+/// endings, is ignored. No IO is done which makes this handy for environments
+/// without a filesystem such as wasm.
+///
+/// # Examples
+/// ```
+/// use spellingbee::solve_from_text;
+/// let answers = solve_from_text("loon\n  pronoun \r\nfoobar\n", 'o', "unrlap");
+///
+/// assert_eq!(2, answers.len());
+/// assert_eq!("pronoun", answers[1].word);
+/// ```
+pub fn solve_from_text(dictionary: &str, required: char, extra: &str) -> Vec<Answer> {
+    find_all(dictionary.lines().map(str::trim), required, extra)
+}
+
+/// A small list of common English words with one word per line, embedded in the
+/// library for environments without a system dictionary.
+#[cfg(feature = "bundled-dict")]
+pub const BUNDLED_WORDS: &str = include_str!("../data/words.txt");
+
+/// Find all valid answers in the bundled word list. This does not touch the
+/// filesystem, but the bundled list is much smaller than a system dictionary
+/// so many answers will be missing.
+///
+/// # Examples
+/// ```
+/// use spellingbee::find_all_default;
+/// let answers = find_all_default('o', "unrlap");
+///
+/// assert!(answers.iter().any(|a| a.word == "popular"));
+/// ```
+#[cfg(feature = "bundled-dict")]
+pub fn find_all_default(required: char, extra: &str) -> Vec<Answer> {
+    solve_from_text(BUNDLED_WORDS, required, extra)
+}
+
+/// Parse a list of answers that were previously serialized as JSON, for
+/// example with `serde_json::to_string`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::answers_from_json;
+/// let answers = answers_from_json(r#"[{"word":"loon","score":1,"is_pangram":false}]"#).unwrap();
+///
+/// assert_eq!("loon", answers[0].word);
+/// ```
+#[cfg(feature = "serde")]
+pub fn answers_from_json(json: &str) -> Result<Vec<Answer>, Error> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// The byte order mark some editors write at the start of UTF-8 files.
+#[cfg(feature = "std")]
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Remove a byte order mark from the start of `line` without reallocating.
+#[cfg(feature = "std")]
+fn strip_bom(mut line: String) -> String {
+    if line.starts_with(BYTE_ORDER_MARK) {
+        line.drain(..BYTE_ORDER_MARK.len_utf8());
+    }
+
+    line
+}
+
+/// Remove leading and trailing whitespace from `line` without reallocating.
+#[cfg(feature = "std")]
+fn trim_line(mut line: String) -> String {
+    line.truncate(line.trim_end().len());
+    line.drain(..line.len() - line.trim_start().len());
+    line
+}
+
+/// Test if the given word is a valid answer to the spelling bee, and return
+/// scoring information if it is an answer. If the word is not an answer than
+/// `None` will be returned.
+///
+/// # Notes
+/// A word is considered an answer if it is at least four letters long, at least
+/// one character matches `required`, and the remaining letters match of the
+/// characters in `extra` or the required character. A pangram is when a
+/// word's letters match both the required character, and every character listed
+/// in `extra`. Puzzles are not limited to seven letters, and a pangram always
+/// needs every letter no matter how many letters are in `extra`. When `extra` is
+/// empty the only answers are words spelled with just the required letter, and
+/// every one of them is a pangram. Leading and trailing ASCII whitespace is
+/// ignored, and the returned answer holds the trimmed word.
+///
+/// Scoring is determined with the following rules:
+///     1. Words of length four are worth one point.
+///     2. Words longer than four letters are worth their length in points.
+///     3. A pangram word gets an additional seven points.
+///
+/// # Examples
+/// ```
+/// use spellingbee::check_word;
+/// assert!(check_word("loon", 'o', "unrlap").is_some());
+/// assert!(check_word("unpopular", 'o', "unrlap").unwrap().is_pangram);
+/// assert_eq!(7, check_word("pronoun", 'o', "unrlap").unwrap().score);
+/// assert!(check_word("foobar", 'o', "unrlap").is_none());
+/// ```
+pub fn check_word(word: &str, required: char, extra: &str) -> Option<Answer> {
+    check_word_with(word, required, extra, &Scoring::default())
+}
+
+/// Version of `check_word` that uses the rules in `scoring` rather than the
+/// default NYT rules to accept and score words.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{check_word_with, Scoring};
+/// let scoring = Scoring {
+///     min_word_len: 3,
+///     ..Scoring::default()
+/// };
+///
+/// assert!(check_word_with("lop", 'o', "unrlap", &scoring).is_some());
+/// ```
+pub fn check_word_with(
+    word: &str,
+    required: char,
+    extra: &str,
+    scoring: &Scoring,
+) -> Option<Answer> {
+    let allowed = AllowedSet::new(extra);
+    let letters = CheckLetters::new(Some(required), extra, &allowed);
+
+    check_word_impl(word, &letters, scoring, |c| c).map(AnswerRef::to_owned)
+}
+
+/// Version of `check_word` that checks `word` against letters that were already
+/// prepared as a `LetterSet`. This avoids scanning the puzzle letters again
+/// when many words are checked against the same puzzle.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{check_word_set, LetterSet};
+/// let letters = LetterSet::new('o', "unrlap");
+///
+/// assert!(check_word_set("loon", &letters).is_some());
+/// assert!(check_word_set("foobar", &letters).is_none());
+/// ```
+pub fn check_word_set(word: &str, letters: &LetterSet) -> Option<Answer> {
+    check_word_set_with(word, letters, &Scoring::default())
+}
+
+/// Version of `check_word_set` that uses the rules in `scoring` rather than the
+/// default NYT rules to accept and score words.
+pub fn check_word_set_with(word: &str, letters: &LetterSet, scoring: &Scoring) -> Option<Answer> {
+    // Puzzles made from ASCII letters have letter masks which lets words be
+    // checked with a few bitwise operations. Other puzzles fall back to
+    // comparing characters one at a time.
+    match &letters.masks {
+        Some(masks) => check_word_masked(word, letters.required, &letters.extra, masks, scoring),
+        None => {
+            let letters =
+                CheckLetters::new(Some(letters.required), &letters.extra, &letters.allowed);
+            check_word_impl(word, &letters, scoring, |c| c).map(AnswerRef::to_owned)
+        }
+    }
+}
+
+/// Version of `check_word` for puzzles that may not have a required letter.
+/// When `required` is `None` any word made up only of `extra` letters is valid,
+/// and pangrams are words that use every letter in `extra`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::check_word_opt;
+/// assert!(check_word_opt("pull", None, "unrlap").is_some());
+/// assert!(check_word_opt("pull", Some('o'), "unrlap").is_none());
+/// ```
+pub fn check_word_opt(word: &str, required: Option<char>, extra: &str) -> Option<Answer> {
+    let allowed = AllowedSet::new(extra);
+    let letters = CheckLetters::new(required, extra, &allowed);
+
+    check_word_impl(word, &letters, &Scoring::default(), |c| c).map(AnswerRef::to_owned)
+}
+
+/// Version of `check_word` for puzzles where one letter is a wildcard. A word
+/// may use `wildcard` like any other puzzle letter, and in addition the
+/// wildcard can stand in for a single letter that is not in the puzzle. The
+/// wildcard covers at most one letter per word, and it cannot stand in for the
+/// required letter. Only the puzzle's own letters count towards a pangram.
+///
+/// # Examples
+/// ```
+/// use spellingbee::check_word_wild;
+/// // "polar" needs one letter (the 'l') that is not in the puzzle.
+/// assert!(check_word_wild("polar", 'o', "unrapy", Some('y')).is_some());
+/// assert!(check_word_wild("polar", 'o', "unrapy", None).is_none());
+/// ```
+pub fn check_word_wild(
+    word: &str,
+    required: char,
+    extra: &str,
+    wildcard: Option<char>,
+) -> Option<Answer> {
+    check_word_forbidden(word, required, extra, wildcard, "")
+}
+
+/// Version of `check_word_wild` for puzzles that also name letters which must
+/// not appear. Any word containing a letter in `forbidden` is rejected before
+/// the puzzle letters are checked, so the wildcard can never stand in for a
+/// forbidden letter.
+///
+/// # Examples
+/// ```
+/// use spellingbee::check_word_forbidden;
+/// assert!(check_word_forbidden("polar", 'o', "unrapy", Some('y'), "c").is_some());
+/// assert!(check_word_forbidden("polar", 'o', "unrapy", Some('y'), "l").is_none());
+/// ```
+pub fn check_word_forbidden(
+    word: &str,
+    required: char,
+    extra: &str,
+    wildcard: Option<char>,
+    forbidden: &str,
+) -> Option<Answer> {
+    let allowed = AllowedSet::new(extra);
+    let letters = CheckLetters {
+        wildcard,
+        forbidden,
+        ..CheckLetters::new(Some(required), extra, &allowed)
+    };
+
+    check_word_impl(word, &letters, &Scoring::default(), |c| c).map(AnswerRef::to_owned)
+}
+
+/// Version of `check_word` that borrows the word from `word` rather than
+/// copying it into a new `String`. This saves an allocation for every answer
+/// when the dictionary words outlive the answers.
+///
+/// # Examples
+/// ```
+/// use spellingbee::check_word_ref;
+/// let dictionary = String::from("unpopular");
+/// let answer = check_word_ref(&dictionary, 'o', "unrlap").unwrap();
+///
+/// assert_eq!("unpopular", answer.word);
+/// assert!(answer.is_pangram);
+/// ```
+pub fn check_word_ref<'a>(word: &'a str, required: char, extra: &str) -> Option<AnswerRef<'a>> {
+    let allowed = AllowedSet::new(extra);
+    let letters = CheckLetters::new(Some(required), extra, &allowed);
+
+    check_word_impl(word, &letters, &Scoring::default(), |c| c)
+}
+
+/// The parts that make up an answer's score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreBreakdown {
+    /// Points given for the length of the word.
+    pub base: i32,
+    /// Points added because the word is a pangram, or zero.
+    pub pangram_bonus: i32,
+    /// Points added for the letters in `Scoring::letter_bonus`, or zero.
+    pub letter_bonus: i32,
+    /// The answer's score, which is the sum of the other parts unless it was
+    /// capped by `Scoring::max_word_score`.
+    pub total: i32,
+}
+
+/// Split the score of `answer` into the points given for its length, the bonus
+/// for being a pangram and the bonus for its letters, using the rules in
+/// `scoring`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{check_word, score_breakdown, Scoring};
+/// let answer = check_word("unpopular", 'o', "unrlap").unwrap();
+/// let breakdown = score_breakdown(&answer, &Scoring::default());
+///
+/// assert_eq!(9, breakdown.base);
+/// assert_eq!(7, breakdown.pangram_bonus);
+/// assert_eq!(answer.score, breakdown.total);
+/// ```
+pub fn score_breakdown(answer: &Answer, scoring: &Scoring) -> ScoreBreakdown {
+    let pangram_letters = answer.is_pangram.then(|| {
+        let word = &answer.word;
+        word.char_indices()
+            .filter(|&(i, w)| !word[..i].chars().any(|p| fold_case(p) == fold_case(w)))
+            .count()
+    });
+
+    score_parts(
+        &answer.word,
+        answer.word.chars().count(),
+        pangram_letters,
+        scoring,
+    )
+}
+
+/// Count how many times the character `c` appears in `word`. Characters are
+/// unicode scalar values, so an accented letter is one character.
+///
+/// # Examples
+/// ```
+/// use spellingbee::count_char;
+/// assert_eq!(3, count_char("unpopular", 'p') + count_char("unpopular", 'a'));
+/// assert_eq!(1, count_char("café", 'é'));
+/// ```
+pub fn count_char(word: &str, c: char) -> usize {
+    word.chars().filter(|&w| w == c).count()
+}
+
+/// Find the puzzle letters that `word` does not use yet, which are the letters
+/// it would need to become a pangram. The required letter comes first if it is
+/// missing, followed by the missing extra letters in the order they appear in
+/// `extra`.
+///
+/// Letters in `word` that are not part of the puzzle are ignored, so a word
+/// that could never be an answer can still have an empty result. Letters are
+/// compared case sensitively like `check_word`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::missing_for_pangram;
+/// assert_eq!(vec!['r', 'a'], missing_for_pangram("unpopul", 'o', "unrlap"));
+/// assert!(missing_for_pangram("unpopular", 'o', "unrlap").is_empty());
+/// ```
+pub fn missing_for_pangram(word: &str, required: char, extra: &str) -> Vec<char> {
+    let mut missing: Vec<char> = Vec::new();
+
+    for c in core::iter::once(required).chain(extra.chars()) {
+        if !word.contains(c) && !missing.contains(&c) {
+            missing.push(c);
+        }
+    }
+
+    missing
+}
+
+/// The most mistakes a word can have and still be a near miss.
+const MAX_NEAR_MISSES: usize = 1;
+
+/// Count how far `word` is from being an answer, for "you're close" hints.
+/// Every character that is not one of the puzzle letters is a mistake, and so
+/// is leaving out the required letter. Returns the number of mistakes when
+/// there is at most one, so answers are `Some(0)` and near misses are
+/// `Some(1)`. Words that are too short to be answers are never near misses.
+///
+/// # Examples
+/// ```
+/// use spellingbee::near_misses;
+/// assert_eq!(Some(0), near_misses("loon", 'o', "unrlap"));
+/// assert_eq!(Some(1), near_misses("lion", 'o', "unrlap"));
+/// assert_eq!(Some(1), near_misses("pull", 'o', "unrlap"));
+/// assert_eq!(None, near_misses("lilt", 'o', "unrlap"));
+/// ```
+pub fn near_misses(word: &str, required: char, extra: &str) -> Option<usize> {
+    let word = trim_word(word);
+
+    if !Scoring::default().allows_length(word.chars().count()) {
+        return None;
+    }
+
+    let disallowed = word
+        .chars()
+        .filter(|&c| c != required && !extra.contains(c))
+        .count();
+    let misses = disallowed + usize::from(!word.contains(required));
+
+    (misses <= MAX_NEAR_MISSES).then_some(misses)
+}
+
+/// The outcome of checking a player's guess with `evaluate_guess`. Rejected
+/// guesses say why they are not an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessResult {
+    /// The guess is an answer.
+    Valid(Answer),
+    /// The guess has fewer letters than an answer needs.
+    TooShort,
+    /// The guess does not use the required letter.
+    MissingRequired,
+    /// The guess uses a letter that is not in the puzzle. Holds the first such
+    /// letter.
+    IllegalLetter(char),
+}
+
+/// Version of `check_word` that explains why a guess is not an answer, so a
+/// player can be told exactly what is wrong. Only the first problem is
+/// returned, checking the length first, then for letters outside the puzzle
+/// and then for the required letter.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{evaluate_guess, GuessResult};
+/// assert!(matches!(evaluate_guess("loon", 'o', "unrlap"), GuessResult::Valid(_)));
+/// assert_eq!(GuessResult::TooShort, evaluate_guess("loo", 'o', "unrlap"));
+/// assert_eq!(GuessResult::IllegalLetter('i'), evaluate_guess("lion", 'o', "unrlap"));
+/// assert_eq!(GuessResult::MissingRequired, evaluate_guess("pull", 'o', "unrlap"));
+/// ```
+pub fn evaluate_guess(word: &str, required: char, extra: &str) -> GuessResult {
+    if let Some(answer) = check_word(word, required, extra) {
+        return GuessResult::Valid(answer);
+    }
+
+    let word = trim_word(word);
+
+    if !Scoring::default().allows_length(word.chars().count()) {
+        GuessResult::TooShort
+    } else if let Some(c) = word.chars().find(|&c| c != required && !extra.contains(c)) {
+        GuessResult::IllegalLetter(c)
+    } else {
+        // Every letter is allowed and the length is fine, so the only thing
+        // left that `check_word` rejects is a missing required letter.
+        GuessResult::MissingRequired
+    }
+}
+
+/// How a submitted word was graded by `grade_submission`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionStatus {
+    /// The word follows the puzzle rules and is in the dictionary.
+    Accepted,
+    /// The word breaks the puzzle rules, for example by being too short or
+    /// using a letter that is not in the puzzle.
+    NotAWord,
+    /// The word follows the puzzle rules but is not in the dictionary.
+    NotInDictionary,
+}
+
+/// Grade every word in `submitted` against the puzzle and `dictionary`. Words
+/// are first checked with `check_word`, and words that pass are then looked up
+/// in the dictionary. Surrounding whitespace is ignored, but letter case must
+/// match. The words are returned in the order they were submitted.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{grade_submission, SubmissionStatus};
+/// use std::collections::HashSet;
+///
+/// let dictionary: HashSet<String> = ["loon".to_string()].into_iter().collect();
+/// let submitted = vec!["loon".to_string(), "loo".to_string(), "noon".to_string()];
+/// let graded = grade_submission(&submitted, &dictionary, 'o', "unrlap");
+///
+/// assert_eq!(SubmissionStatus::Accepted, graded[0].1);
+/// assert_eq!(SubmissionStatus::NotAWord, graded[1].1);
+/// assert_eq!(SubmissionStatus::NotInDictionary, graded[2].1);
+/// ```
+#[cfg(feature = "std")]
+pub fn grade_submission(
+    submitted: &[String],
+    dictionary: &HashSet<String>,
+    required: char,
+    extra: &str,
+) -> Vec<(String, SubmissionStatus)> {
+    submitted
+        .iter()
+        .map(|word| {
+            let status = if check_word(word, required, extra).is_none() {
+                SubmissionStatus::NotAWord
+            } else if !dictionary.contains(trim_word(word)) {
+                SubmissionStatus::NotInDictionary
+            } else {
+                SubmissionStatus::Accepted
+            };
+
+            (word.clone(), status)
+        })
+        .collect()
+}
+
+/// Count how many different puzzle letters `word` uses, including the required
+/// letter. A pangram uses all of them. Letters are compared case sensitively
+/// like `check_word`, and characters that are not puzzle letters are ignored.
+///
+/// # Examples
+/// ```
+/// use spellingbee::distinct_puzzle_letters;
+/// assert_eq!(3, distinct_puzzle_letters("loon", 'o', "unrlap"));
+/// assert_eq!(7, distinct_puzzle_letters("unpopular", 'o', "unrlap"));
+/// ```
+pub fn distinct_puzzle_letters(word: &str, required: char, extra: &str) -> usize {
+    puzzle_letters_used(trim_word(word), Some(required), extra, |c| c)
+}
+
+/// Get the different puzzle letters that `word` uses, including the required
+/// letter, in sorted order. Letters are compared case sensitively like
+/// `check_word`, and characters that are not puzzle letters are ignored.
+///
+/// # Examples
+/// ```
+/// use spellingbee::letters_used;
+/// assert_eq!(vec!['l', 'n', 'o'], letters_used("loon", 'o', "unrlap"));
+/// ```
+pub fn letters_used(word: &str, required: char, extra: &str) -> Vec<char> {
+    let mut letters: Vec<char> =
+        puzzle_letters_in(trim_word(word), Some(required), extra, |c| c).collect();
+    letters.sort_unstable();
+    letters.dedup();
+    letters
+}
+
+/// Test if `word` is made up entirely of alphabetic characters. Dictionary
+/// entries like "can't" or "mother-in-law" can never be spelling bee answers,
+/// and this cheap test lets callers skip them before calling `check_word`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::is_candidate_word;
+/// assert!(is_candidate_word("loon"));
+/// assert!(!is_candidate_word("can't"));
+/// ```
+pub fn is_candidate_word(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(char::is_alphabetic)
+}
+
+/// Validating version of `check_word` that returns an error rather than a
+/// possibly incorrect answer when `required` and `extra` do not describe a
+/// valid puzzle. See `PuzzleError` for the kinds of misconfigured puzzles that
+/// are detected.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{try_check_word, PuzzleError};
+/// assert!(try_check_word("loon", 'o', "unrlap").unwrap().is_some());
+/// assert_eq!(
+///     Err(PuzzleError::RequiredInExtra('o')),
+///     try_check_word("loon", 'o', "unrlop")
+/// );
+/// ```
+pub fn try_check_word(
+    word: &str,
+    required: char,
+    extra: &str,
+) -> Result<Option<Answer>, PuzzleError> {
+    validate_puzzle(required, extra)?;
+    Ok(check_word(word, required, extra))
+}
+
+/// Verify `required` and `extra` form a valid set of puzzle letters. Every
+/// letter must be alphabetic, `extra` cannot be empty and no letter can be
+/// listed more than once. The first problem found is returned as an error.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{validate_puzzle, PuzzleError};
+/// assert!(validate_puzzle('o', "unrlap").is_ok());
+/// assert_eq!(Err(PuzzleError::NotAlphabetic('3')), validate_puzzle('o', "unr3ap"));
+/// ```
+pub fn validate_puzzle(required: char, extra: &str) -> Result<(), PuzzleError> {
+    if let Some(c) = core::iter::once(required)
+        .chain(extra.chars())
+        .find(|c| !c.is_alphabetic())
+    {
+        return Err(PuzzleError::NotAlphabetic(c));
+    }
+
+    if extra.is_empty() {
+        return Err(PuzzleError::EmptyExtra);
+    }
+
+    for (i, e) in extra.char_indices() {
+        if e == required {
+            return Err(PuzzleError::RequiredInExtra(e));
+        }
+
+        if extra[..i].contains(e) {
+            return Err(PuzzleError::DuplicateExtra(e));
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove everything from `input` except letters, such as whitespace (including
+/// unicode spaces), zero-width characters and punctuation copied along with the
+/// puzzle letters from a web page.
+///
+/// # Examples
+/// ```
+/// use spellingbee::sanitize_letters;
+/// assert_eq!("ounrlap", sanitize_letters(" \"O\u{a0}unrlap!\" ").to_lowercase());
+/// ```
+pub fn sanitize_letters(input: &str) -> String {
+    input.chars().filter(|c| c.is_alphabetic()).collect()
+}
+
+/// Split a standard seven letter puzzle such as "telomic" into its required
+/// center letter (the first letter) and the six outer letters. An error is
+/// returned unless `puzzle` is exactly seven distinct alphabetic letters.
+///
+/// # Examples
+/// ```
+/// use spellingbee::{parse_puzzle, PuzzleError};
+/// assert_eq!(Ok(('o', "unrlap".to_string())), parse_puzzle("ounrlap"));
+/// assert_eq!(Err(PuzzleError::WrongLetterCount(3)), parse_puzzle("our"));
+/// ```
+pub fn parse_puzzle(puzzle: &str) -> Result<(char, String), PuzzleError> {
+    let letter_count = puzzle.chars().count();
+
+    if letter_count != PUZZLE_LETTER_COUNT {
+        return Err(PuzzleError::WrongLetterCount(letter_count));
+    }
+
+    let mut letters = puzzle.chars();
+    let required = letters.next().unwrap();
+    let extra: String = letters.collect();
+
+    validate_puzzle(required, &extra)?;
+    Ok((required, extra))
+}
+
+/// Case insensitive version of `check_word`. The word, the required character
+/// and the extra characters are all lowercased before they are compared, which
+/// means dictionary entries like "Tote" or "MOTEL" are accepted. The returned
+/// answer keeps the word's original casing.
+///
+/// # Examples
+/// ```
+/// use spellingbee::check_word_ci;
+/// assert_eq!("Loon", check_word_ci("Loon", 'o', "unrlap").unwrap().word);
+/// assert!(check_word_ci("UNPOPULAR", 'O', "unrlap").unwrap().is_pangram);
+/// ```
+pub fn check_word_ci(word: &str, required: char, extra: &str) -> Option<Answer> {
+    check_word_ci_fn(required, extra)(word)
+}
+
+/// Build a case insensitive version of `check_word` for one puzzle. The folded
+/// puzzle letters are prepared once, so calling the returned function for many
+/// words does not rebuild them each time.
+fn check_word_ci_fn(required: char, extra: &str) -> impl Fn(&str) -> Option<Answer> + '_ {
+    let allowed = folded_set(extra, fold_case);
+
+    move |word| {
+        let letters = CheckLetters::new(Some(required), extra, &allowed);
+        check_word_impl(word, &letters, &Scoring::default(), fold_case).map(AnswerRef::to_owned)
+    }
+}
+
+/// Lowercase a single character. Characters that lowercase to multiple
+/// characters keep only the first so word lengths are unchanged.
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Remove leading and trailing ASCII whitespace from `word`. Whitespace inside
+/// of the word is kept, which means the word will not be a valid answer.
+fn trim_word(word: &str) -> &str {
+    word.trim_matches(|c: char| c.is_ascii_whitespace())
+}
+
+/// Build an `AllowedSet` of `letters` after each one is passed through `fold`.
+fn folded_set(letters: &str, fold: fn(char) -> char) -> AllowedSet {
+    letters.chars().map(fold).collect()
+}
+
+/// The puzzle letters checked by `check_word_impl`. Words do not need to
+/// contain a required letter when `required` is `None`, one letter not in the
+/// puzzle is allowed when there is a `wildcard` and words using any letter in
+/// `forbidden` are never answers. `allowed` holds the `extra` letters after
+/// they were passed through the same `fold` used to check words, so it is built
+/// once per puzzle rather than once per word.
+struct CheckLetters<'a> {
+    required: Option<char>,
+    extra: &'a str,
+    allowed: &'a AllowedSet,
+    wildcard: Option<char>,
+    forbidden: &'a str,
+}
+
+impl<'a> CheckLetters<'a> {
+    /// Letters for a puzzle without a wildcard or forbidden letters.
+    fn new(required: Option<char>, extra: &'a str, allowed: &'a AllowedSet) -> CheckLetters<'a> {
+        CheckLetters {
+            required,
+            extra,
+            allowed,
+            wildcard: None,
+            forbidden: "",
+        }
+    }
+}
+
+/// Shared implementation of `check_word_with`, `check_word_opt` and
+/// `check_word_ci`. Every character is passed through `fold` before being
+/// compared.
+fn check_word_impl<'a>(
+    word: &'a str,
+    letters: &CheckLetters,
+    scoring: &Scoring,
+    fold: fn(char) -> char,
+) -> Option<AnswerRef<'a>> {
+    let extra = letters.extra;
+    let forbidden = letters.forbidden;
+
+    let word = trim_word(word);
+
+    // Words and puzzle letters that are not in NFC form are normalized first,
+    // but the answer keeps the word as it was written in the dictionary.
+    #[cfg(feature = "unicode")]
+    {
+        use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+        if !is_nfc(word) || !is_nfc(extra) || !is_nfc(forbidden) {
+            let normalized: String = word.nfc().collect();
+            let extra: String = extra.nfc().collect();
+            let forbidden: String = forbidden.nfc().collect();
+            let allowed = folded_set(&extra, fold);
+            let letters = CheckLetters {
+                extra: &extra,
+                allowed: &allowed,
+                forbidden: &forbidden,
+                ..*letters
+            };
+
+            return check_word_impl(&normalized, &letters, scoring, fold).map(|ans| AnswerRef {
+                word,
+                score: ans.score,
+                is_pangram: ans.is_pangram,
+            });
+        }
+    }
+
+    // Words containing a forbidden letter are rejected before anything else,
+    // which also stops the wildcard from covering them.
+    if word
+        .chars()
+        .any(|w| forbidden.chars().any(|f| fold(f) == fold(w)))
+    {
+        return None;
+    }
+
+    let required = letters.required.map(fold);
+    let wildcard = letters.wildcard.map(fold);
+
+    // Words must be at least `min_word_len` characters, and no longer than the
+    // optional `max_word_len`. Length is measured in unicode scalar values
+    // rather than bytes so accented letters count once.
+    let word_len = word.chars().count();
+
+    if !scoring.allows_length(word_len) {
+        return None;
+    }
+
+    // Words must also contain the required character if there is one, and
+    // maybe more than once.
+    if let Some(required) = required {
+        let occurrences = word.chars().filter(|&w| fold(w) == required).count();
+
+        if occurrences < scoring.min_required_occurrences.max(1) {
+            return None;
+        }
+    }
+
+    // Words can only contain characters matching required or extra, except
+    // for a single character that can be covered by the wildcard. Searching for
+    // the character after the ones the wildcard can cover stops as soon as a
+    // word needs too many.
+    let wildcard_uses = usize::from(wildcard.is_some());
+
+    if word
+        .chars()
+        .map(fold)
+        .filter(|&x| Some(x) != required && Some(x) != wildcard && !letters.allowed.contains(x))
+        .nth(wildcard_uses)
+        .is_none()
+    {
+        let required_count = usize::from(required.is_some());
+        let uniq_count = puzzle_letters_used(word, required, extra, fold);
+        let is_pangram = uniq_count == required_count + extra.chars().count();
+
+        if scoring.pangrams_only && !is_pangram {
+            return None;
+        }
+
+        // Optionally require the word to have a minimum number of different
+        // letters. Only the first occurrence of each letter is counted.
+        if scoring.min_distinct_letters > 0 {
+            let distinct_count = word
+                .char_indices()
+                .filter(|&(i, w)| !word[..i].chars().any(|p| fold(p) == fold(w)))
+                .count();
+
+            if distinct_count < scoring.min_distinct_letters {
+                return None;
+            }
+        }
+
+        // Return answer as the word, its score and if it was a pangram.
+        Some(AnswerRef {
+            word,
+            score: score_word(word, word_len, is_pangram.then_some(uniq_count), scoring),
+            is_pangram,
+        })
+    } else {
+        None
+    }
+}
+
+/// Count the number of puzzle letters that appear in `word` after every
+/// character is passed through `fold`. `required` must already be folded.
+fn puzzle_letters_used(
+    word: &str,
+    required: Option<char>,
+    extra: &str,
+    fold: fn(char) -> char,
+) -> usize {
+    puzzle_letters_in(word, required, extra, fold).count()
+}
+
+/// Iterate over the puzzle letters that appear in `word` after every character
+/// is passed through `fold`, starting with `required` and then the extra
+/// letters in the order they are listed. `required` must already be folded.
+fn puzzle_letters_in<'a>(
+    word: &'a str,
+    required: Option<char>,
+    extra: &'a str,
+    fold: fn(char) -> char,
+) -> impl Iterator<Item = char> + 'a {
+    // Find the letters that were matched. We do this with a O(nm) algorithm to
+    // avoid allocating a hashmap since both n and m are small.
+    let required_used = required.filter(|&r| word.chars().any(|w| fold(w) == r));
+
+    required_used.into_iter().chain(
+        extra
+            .chars()
+            .filter(move |&e| word.chars().any(|w| fold(w) == fold(e))),
+    )
+}
+
+/// Calculate the score of an answer `word` that is `word_len` characters long.
+/// Pangrams pass the number of different letters they use as
+/// `pangram_letters`.
+fn score_word(
+    word: &str,
+    word_len: usize,
+    pangram_letters: Option<usize>,
+    scoring: &Scoring,
+) -> i32 {
+    score_parts(word, word_len, pangram_letters, scoring).total
+}
+
+/// Calculate each part of the score of an answer `word` that is `word_len`
+/// characters long. Pangrams pass the number of different letters they use as
+/// `pangram_letters`.
+fn score_parts(
+    word: &str,
+    word_len: usize,
+    pangram_letters: Option<usize>,
+    scoring: &Scoring,
+) -> ScoreBreakdown {
+    // Scoring uses the following rules (default values in parentheses):
+    //  1. Short words score `short_word_score` (1) points.
+    //  2. Words at least `long_word_min_len` (5) letters long score their
+    //     length in points.
+    //  3. A pangram receives extra points from `pangram_bonus_mode`, which is
+    //     a flat (7) points by default.
+    //  4. Each different letter in the word adds its `letter_bonus` (none).
+    //  5. The total is capped at `max_word_score` (no cap).
+    let base = if word_len >= scoring.long_word_min_len {
+        word_len as i32
+    } else {
+        scoring.short_word_score
+    };
+
+    let pangram_bonus = pangram_letters.map_or(0, |n| scoring.pangram_bonus_mode.points(n));
+
+    let letter_bonus = if scoring.letter_bonus.is_empty() {
+        0
+    } else {
+        word.char_indices()
+            .filter(|&(i, c)| !word[..i].contains(c))
+            .filter_map(|(_, c)| scoring.letter_bonus.get(&c))
+            .sum()
+    };
+
+    let total = base + pangram_bonus + letter_bonus;
+
+    ScoreBreakdown {
+        base,
+        pangram_bonus,
+        letter_bonus,
+        total: scoring.max_word_score.map_or(total, |cap| total.min(cap)),
+    }
+}
+
+/// The letters of a puzzle prepared once so that many words can be checked
+/// against them quickly. Unlike `Puzzle` the letters are not validated, which
+/// matches the behavior of the string based functions like `check_word`.
+///
+/// # Examples
+/// ```
+/// use spellingbee::LetterSet;
+/// let letters = LetterSet::new('o', "unrlap");
+///
+/// assert!(letters.contains('u'));
+/// assert!(!letters.contains('z'));
+/// assert_eq!(7, letters.pangram_size());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterSet {
+    required: char,
+    extra: String,
+    allowed: AllowedSet,
+    masks: Option<LetterMasks>,
+}
+
+impl LetterSet {
+    /// Prepare the `required` and `extra` letters of a puzzle.
+    pub fn new(required: char, extra: &str) -> LetterSet {
+        LetterSet {
+            required,
+            extra: extra.to_string(),
+            allowed: AllowedSet::new(extra),
+            masks: LetterMasks::new(required, extra),
+        }
+    }
+
+    /// The letter that must appear in every answer.
+    pub fn required(&self) -> char {
+        self.required
+    }
+
+    /// The other letters allowed to appear in an answer.
+    pub fn extra(&self) -> &str {
+        &self.extra
+    }
+
+    /// Test if `c` is one of the puzzle's letters, either the required letter
+    /// or one of the extra letters.
+    pub fn contains(&self, c: char) -> bool {
+        match (&self.masks, letter_bit(c)) {
+            (Some(masks), Some(bit)) => masks.allowed & bit != 0,
+            (Some(_), None) => false,
+            _ => c == self.required || self.allowed.contains(c),
+        }
+    }
+
+    /// The number of different letters in the puzzle, which is the number of
+    /// letters a pangram must use.
+    pub fn pangram_size(&self) -> usize {
+        match &self.masks {
+            Some(masks) => masks.allowed.count_ones() as usize,
+            None => {
+                let mut letters: Vec<char> = self.extra.chars().collect();
+                letters.push(self.required);
+                letters.sort_unstable();
+                letters.dedup();
+                letters.len()
+            }
+        }
+    }
+}
+
+/// A small set of letters with a fast membership test. Sets made only of ASCII
+/// characters use a lookup table, and any other set falls back to scanning its
+/// letters one at a time.
+///
+/// # Examples
+/// ```
+/// use spellingbee::AllowedSet;
+/// let allowed = AllowedSet::new("unrlap");
+///
+/// assert!(allowed.is_ascii());
+/// assert!(allowed.contains('u'));
+/// assert!(!allowed.contains('o'));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowedSet {
+    letters: AllowedLetters,
+}
+
+/// Storage for the letters in an `AllowedSet`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AllowedLetters {
+    Ascii([bool; 128]),
+    Chars(Vec<char>),
+}
+
+impl AllowedSet {
+    /// Build a set holding every character in `letters`.
+    pub fn new(letters: &str) -> AllowedSet {
+        letters.chars().collect()
+    }
+
+    /// Test if the set uses the ASCII lookup table rather than scanning.
+    pub fn is_ascii(&self) -> bool {
+        matches!(self.letters, AllowedLetters::Ascii(_))
+    }
+
+    /// Test if `c` is in the set.
+    pub fn contains(&self, c: char) -> bool {
+        match &self.letters {
+            AllowedLetters::Ascii(table) => c.is_ascii() && table[c as usize],
+            AllowedLetters::Chars(chars) => chars.contains(&c),
+        }
+    }
+}
+
+impl FromIterator<char> for AllowedSet {
+    fn from_iter<I: IntoIterator<Item = char>>(letters: I) -> Self {
+        // ASCII letters go straight into the lookup table, so sets made only of
+        // ASCII letters never allocate.
+        let mut table = [false; 128];
+        let mut others: Vec<char> = Vec::new();
+
+        for c in letters {
+            if c.is_ascii() {
+                table[c as usize] = true;
+            } else {
+                others.push(c);
+            }
+        }
+
+        let letters = if others.is_empty() {
+            AllowedLetters::Ascii(table)
+        } else {
+            others.extend((0..128u8).filter(|&b| table[b as usize]).map(char::from));
+            AllowedLetters::Chars(others)
+        };
+
+        AllowedSet { letters }
+    }
+}
+
+/// Bitmasks for the letters in a puzzle where every letter is an ASCII letter.
+/// See `letter_mask` for how letters are mapped to bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LetterMasks {
+    required: u64,
+    allowed: u64,
+}
+
+impl LetterMasks {
+    /// Build masks for a puzzle, or return `None` if any of the puzzle's
+    /// letters is not an ASCII letter.
+    fn new(required: char, extra: &str) -> Option<LetterMasks> {
+        let required = letter_bit(required)?;
+        let extra = letter_mask(extra)?;
+
+        Some(LetterMasks {
+            required,
+            allowed: required | extra,
+        })
+    }
+}
+
+/// Get the bit representing `c` in a letter mask, or `None` when `c` is not an
+/// ASCII letter. Lowercase letters use bits 0-25 and uppercase letters bits
+/// 26-51, which keeps the masks case sensitive like `check_word`.
+fn letter_bit(c: char) -> Option<u64> {
+    match c {
+        'a'..='z' => Some(1 << (c as u32 - 'a' as u32)),
+        'A'..='Z' => Some(1 << (26 + c as u32 - 'A' as u32)),
+        _ => None,
+    }
+}
+
+/// Build a mask with the bit for every letter in `s` set, or return `None` if
+/// `s` contains anything other than ASCII letters.
+fn letter_mask(s: &str) -> Option<u64> {
+    s.chars().try_fold(0, |mask, c| Some(mask | letter_bit(c)?))
+}
+
+/// Fast path for `check_word_with` that compares a word's letters against the
+/// puzzle using bitmasks. Words with characters other than ASCII letters are
+/// checked with `check_word_with` instead.
+fn check_word_masked(
+    word: &str,
+    required: char,
+    extra: &str,
+    masks: &LetterMasks,
+    scoring: &Scoring,
+) -> Option<Answer> {
+    let word = trim_word(word);
+
+    match letter_mask(word) {
+        // Every character is ASCII so the byte length matches the char count.
+        Some(word_mask) => check_mask(word, word_mask, word.len(), required, masks, scoring),
+        None => check_word_with(word, required, extra, scoring),
+    }
+}
+
+/// Check a word made up only of ASCII letters against the puzzle `masks`, where
+/// `word_mask` holds the word's letters and `word_len` is its length.
+fn check_mask(
+    word: &str,
+    word_mask: u64,
+    word_len: usize,
+    required: char,
+    masks: &LetterMasks,
+    scoring: &Scoring,
+) -> Option<Answer> {
+    let is_pangram = word_mask & masks.allowed == masks.allowed;
+
+    if !scoring.allows_length(word_len)
+        || word_mask & !masks.allowed != 0
+        || word_mask & masks.required == 0
+        || (word_mask.count_ones() as usize) < scoring.min_distinct_letters
+        || (scoring.pangrams_only && !is_pangram)
+    {
+        return None;
+    }
+
+    if scoring.min_required_occurrences > 1
+        && count_char(word, required) < scoring.min_required_occurrences
+    {
+        return None;
+    }
+
+    let pangram_letters = is_pangram.then_some(masks.allowed.count_ones() as usize);
+
+    Some(Answer {
+        word: word.to_string(),
+        score: score_word(word, word_len, pangram_letters, scoring),
+        is_pangram,
+    })
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn empty_word_is_not_valid() {
+        assert_eq!(None, check_word("", 'c', "ab"));
+    }
+
+    #[test]
+    fn word_less_than_four_chars_not_valid() {
+        assert_eq!(None, check_word("c", 'c', "ab"));
+        assert_eq!(None, check_word("ca", 'c', "ab"));
+        assert_eq!(None, check_word("cab", 'c', "ab"));
+
+        assert_eq!(None, check_word("d", 'c', "ab"));
+        assert_eq!(None, check_word("do", 'c', "ab"));
+        assert_eq!(None, check_word("dog", 'c', "ab"));
+    }
+
+    #[test]
+    fn word_uses_required_valid() {
+        assert!(check_word("tttt", 't', "oe").is_some());
+        assert!(check_word("tttttt", 't', "oe").is_some());
+        assert!(check_word("ssss", 's', "oe").is_some());
+    }
+
+    #[test]
+    fn word_missing_required_not_valid() {
+        assert_eq!(None, check_word("oeoe", 't', "oe"));
+        assert_eq!(None, check_word("oooo", 't', "oe"));
+    }
+
+    #[test]
+    fn word_can_use_extra_chars() {
+        assert!(check_word("tote", 't', "elom").is_some());
+        assert!(check_word("totet", 't', "elom").is_some());
+        assert!(check_word("mote", 't', "elom").is_some());
+        assert!(check_word("molet", 't', "elom").is_some());
+    }
+
+    #[test]
+    fn word_with_chars_not_in_required_or_extra_not_valid() {
+        assert_eq!(None, check_word("dote", 't', "elom"));
+        assert_eq!(None, check_word("note", 't', "elom"));
+    }
+
+    #[test]
+    fn test_multiple_words() {
+        let words = [
+            "tote".to_string(),
+            "vote".to_string(),
+            "mote".to_string(),
+            "soapy".to_string(),
+        ];
+        let answers = find_all(words.iter(), 't', "elom");
+        assert_eq!(2, answers.len());
+        assert_eq!("tote", answers[0].word);
+        assert_eq!("mote", answers[1].word);
+    }
+
+    #[test]
+    fn find_all_keeps_duplicates() {
+        let answers = find_all(["tote", "mote", "tote"], 't', "elom");
+        assert_eq!(3, answers.len());
+    }
+
+    #[test]
+    fn find_all_unique_skips_duplicates() {
+        let words = ["tote", "mote", "tote", "vote", "mote", "Tote"];
+        let answers = find_all_unique(words, 't', "elom");
+        assert_eq!(2, answers.len());
+        assert_eq!("tote", answers[0].word);
+        assert_eq!("mote", answers[1].word);
+    }
+
+    #[test]
+    fn answer_contains_original_word() {
+        assert_eq!("motel", check_word("motel", 't', "elom").unwrap().word);
+        assert_eq!("tome", check_word("tome", 't', "elom").unwrap().word);
+    }
+
+    #[test]
+    fn pangram_uses_all_letters() {
+        assert!(check_word("motel", 't', "elom").unwrap().is_pangram);
+        assert!(check_word("emotel", 't', "elom").unwrap().is_pangram);
+        assert!(!check_word("motee", 't', "elom").unwrap().is_pangram);
+        assert!(!check_word("mote", 't', "elom").unwrap().is_pangram);
+    }
+
+    #[test]
+    fn four_length_score_is_one() {
+        assert_eq!(1, check_word("tome", 't', "elom").unwrap().score);
+        assert_eq!(1, check_word("tell", 't', "elom").unwrap().score);
+    }
+
+    #[test]
+    fn score_equal_length_when_larger_than_four() {
+        assert_eq!(5, check_word("motee", 't', "elom").unwrap().score);
+        assert_eq!(5, check_word("tello", 't', "elom").unwrap().score);
+        assert_eq!(6, check_word("tomtom", 't', "elom").unwrap().score);
+        assert_eq!(9, check_word("tomtomtom", 't', "elom").unwrap().score);
+    }
+
+    #[test]
+    fn pangram_adds_seven_to_score() {
+        assert_eq!(12, check_word("motel", 't', "elom").unwrap().score);
+        assert_eq!(13, check_word("emotel", 't', "elom").unwrap().score);
+    }
+
+    #[test]
+    fn length_counts_chars_not_bytes() {
+        // "café" is four characters but five bytes long.
+        assert!(check_word("caf\u{e9}", 'c', "af\u{e9}").is_some());
+        assert!(check_word("\u{e9}t\u{e9}", '\u{e9}', "t").is_none());
+    }
+
+    #[test]
+    fn score_uses_char_length() {
+        assert_eq!(1, check_word("caf\u{e9}", 'c', "af\u{e9}x").unwrap().score);
+        assert_eq!(
+            5,
+            check_word("na\u{ef}ve", 'n', "a\u{ef}vex").unwrap().score
+        );
+    }
+
+    #[test]
+    fn pangram_counts_chars_not_bytes() {
+        assert!(check_word("caf\u{e9}", 'c', "af\u{e9}").unwrap().is_pangram);
+    }
+
+    #[test]
+    fn case_sensitive_by_default() {
+        assert_eq!(None, check_word("Tote", 't', "elom"));
+        assert_eq!(None, check_word("tote", 'T', "elom"));
+    }
+
+    #[test]
+    fn case_insensitive_accepts_mixed_case() {
+        assert!(check_word_ci("Tote", 't', "elom").is_some());
+        assert!(check_word_ci("tOtE", 't', "elom").is_some());
+        assert!(check_word_ci("tote", 'T', "ELOM").is_some());
+        assert_eq!(None, check_word_ci("Dote", 't', "elom"));
+    }
+
+    #[test]
+    fn case_insensitive_accepts_all_caps() {
+        assert!(check_word_ci("TOTE", 't', "elom").is_some());
+        assert_eq!(5, check_word_ci("TOTEM", 't', "elom").unwrap().score);
+    }
+
+    #[test]
+    fn case_insensitive_keeps_original_word() {
+        assert_eq!("Motel", check_word_ci("Motel", 't', "elom").unwrap().word);
+        assert_eq!("TOME", check_word_ci("TOME", 't', "elom").unwrap().word);
+    }
+
+    #[test]
+    fn case_insensitive_pangram() {
+        assert!(check_word_ci("MoTeL", 't', "elom").unwrap().is_pangram);
+        assert!(check_word_ci("motel", 'T', "ELOM").unwrap().is_pangram);
+        assert!(!check_word_ci("MOTE", 't', "elom").unwrap().is_pangram);
+        assert_eq!(12, check_word_ci("MOTEL", 't', "elom").unwrap().score);
+    }
+
+    #[test]
+    fn find_all_ci_keeps_capitalized_words() {
+        let answers = find_all_ci(["Tote", "vote", "MOTE"], 't', "elom");
+        assert_eq!(2, answers.len());
+        assert_eq!("Tote", answers[0].word);
+        assert_eq!("MOTE", answers[1].word);
+    }
+
+    #[test]
+    fn try_check_word_valid_puzzle() {
+        assert!(try_check_word("tote", 't', "elom").unwrap().is_some());
+        assert_eq!(Ok(None), try_check_word("vote", 't', "elom"));
+    }
+
+    #[test]
+    fn try_check_word_required_in_extra() {
+        assert_eq!(
+            Err(PuzzleError::RequiredInExtra('t')),
+            try_check_word("tote", 't', "eltom")
+        );
+    }
+
+    #[test]
+    fn try_check_word_duplicate_extra() {
+        assert_eq!(
+            Err(PuzzleError::DuplicateExtra('e')),
+            try_check_word("tote", 't', "elome")
+        );
+    }
+
+    #[test]
+    fn try_check_word_empty_extra() {
+        assert_eq!(
+            Err(PuzzleError::EmptyExtra),
+            try_check_word("tttt", 't', "")
+        );
+    }
+
+    #[test]
+    fn default_scoring_matches_check_word() {
+        for w in ["tome", "motee", "motel", "tomtomtom", "vote"] {
+            assert_eq!(
+                check_word(w, 't', "elom"),
+                check_word_with(w, 't', "elom", &Scoring::default())
+            );
+        }
+    }
+
+    #[test]
+    fn custom_scoring_min_word_len() {
+        let scoring = Scoring {
+            min_word_len: 3,
+            ..Scoring::default()
+        };
+
+        assert_eq!(
+            1,
+            check_word_with("tom", 't', "elom", &scoring).unwrap().score
+        );
+        assert_eq!(None, check_word_with("to", 't', "elom", &scoring));
+    }
+
+    #[test]
+    fn custom_scoring_flat_word_score() {
+        let scoring = Scoring {
+            long_word_min_len: usize::MAX,
+            short_word_score: 2,
+            pangram_bonus_mode: PangramBonus::Flat(3),
+            ..Scoring::default()
+        };
+
+        assert_eq!(
+            2,
+            check_word_with("tome", 't', "elom", &scoring)
+                .unwrap()
+                .score
+        );
+        assert_eq!(
+            2,
+            check_word_with("tomtom", 't', "elom", &scoring)
+                .unwrap()
+                .score
+        );
+        assert_eq!(
+            5,
+            check_word_with("motel", 't', "elom", &scoring)
+                .unwrap()
+                .score
+        );
+    }
+
+    #[test]
+    fn find_all_iter_matches_find_all() {
+        let words = ["tote", "vote", "mote", "soapy", "motel"];
+        assert_eq!(
+            find_all(words, 't', "elom"),
+            find_all_iter(words, 't', "elom").collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn find_all_iter_is_lazy() {
+        let words = ["vote", "tote", "mote", "motel", "tome"];
+        let read_count = Cell::new(0);
+        let mut answers = find_all_iter(
+            words
+                .iter()
+                .inspect(|_| read_count.set(read_count.get() + 1)),
+            't',
+            "elom",
+        );
+
+        assert_eq!(0, read_count.get());
+        assert_eq!("tote", answers.next().unwrap().word);
+        assert_eq!(2, read_count.get());
+    }
+
+    #[test]
+    fn summarize_empty_answers() {
+        assert_eq!(Summary::default(), summarize(&[]));
+    }
+
+    #[test]
+    fn summarize_counts_words_pangrams_and_score() {
+        let answers = find_all(["tote", "motel", "vote", "tomtom", "emotel"], 't', "elom");
+        assert_eq!(
+            Summary {
+                word_count: 4,
+                pangram_count: 2,
+                total_score: 32,
+            },
+            summarize(&answers)
+        );
+    }
+
+    #[test]
+    fn letter_masks_require_ascii_letters() {
+        assert!(LetterMasks::new('t', "elom").is_some());
+        assert!(LetterMasks::new('T', "ELOM").is_some());
+        assert!(LetterMasks::new('\u{e9}', "elom").is_none());
+        assert!(LetterMasks::new('t', "el0m").is_none());
+    }
+
+    #[test]
+    fn masked_check_matches_scalar_check() {
+        let puzzles = [
+            ('t', "elom"),
+            ('o', "unrlap"),
+            ('T', "eLom"),
+            ('t', "eltom"),
+        ];
+        let words = [
+            "",
+            "tote",
+            "vote",
+            "mote",
+            "motel",
+            "emotel",
+            "tomtomtom",
+            "oooo",
+            "loon",
+            "pronoun",
+            "unpopular",
+            "foobar",
+            "Tote",
+            "TOTE",
+            "mOTEL",
+            "caf\u{e9}",
+            "to-te",
+            "t\u{f6}te",
+            "tot",
+            "lollop",
+        ];
+        let scoring = Scoring::default();
+
+        for (required, extra) in puzzles {
+            let masks = LetterMasks::new(required, extra).unwrap();
+
+            for w in words {
+                assert_eq!(
+                    check_word_with(w, required, extra, &scoring),
+                    check_word_masked(w, required, extra, &masks, &scoring),
+                    "word {:?} puzzle {} {}",
+                    w,
+                    required,
+                    extra
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn find_all_falls_back_for_non_ascii_puzzles() {
+        let answers = find_all(["caf\u{e9}", "cafe"], 'c', "af\u{e9}");
+        assert_eq!(1, answers.len());
+        assert!(answers[0].is_pangram);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn find_all_par_matches_find_all() {
+        let words = [
+            "tote",
+            "vote",
+            "mote",
+            "soapy",
+            "motel",
+            "emotel",
+            "tome",
+            "tomtom",
+            "lotto",
+            "caf\u{e9}",
+            "mellow",
+            "toll",
+        ];
+        let mut expected = find_all(words, 't', "elom");
+        let mut actual = crate::find_all_par(words, 't', "elom");
+
+        expected.sort_by(|a, b| a.word.cmp(&b.word));
+        actual.sort_by(|a, b| a.word.cmp(&b.word));
+        assert_eq!(expected, actual);
+    }
+
+    fn words_of(answers: &[Answer]) -> Vec<&str> {
+        answers.iter().map(|a| a.word.as_str()).collect()
+    }
+
+    #[test]
+    fn filter_answers_sorts_by_score() {
+        let answers = find_all(["tote", "motel", "tomtom", "mote"], 't', "elom");
+        assert_eq!(
+            vec!["motel", "tomtom", "mote", "tote"],
+            words_of(&filter_answers(answers, None, None))
+        );
+    }
+
+    #[test]
+    fn filter_answers_min_score() {
+        let answers = find_all(["tote", "motel", "tomtom", "mote"], 't', "elom");
+        assert_eq!(
+            vec!["motel", "tomtom"],
+            words_of(&filter_answers(answers, Some(6), None))
+        );
+    }
+
+    #[test]
+    fn filter_answers_top() {
+        let answers = find_all(["tote", "motel", "tomtom", "mote"], 't', "elom");
+        assert_eq!(
+            vec!["motel", "tomtom", "mote"],
+            words_of(&filter_answers(answers, None, Some(3)))
+        );
+    }
+
+    #[test]
+    fn filter_answers_top_zero_is_empty() {
+        let answers = find_all(["tote", "motel"], 't', "elom");
+        assert!(filter_answers(answers, None, Some(0)).is_empty());
+    }
+
+    #[test]
+    fn filter_answers_top_ties_are_deterministic() {
+        let a = find_all(["tote", "tome", "mote", "toll"], 't', "elom");
+        let b = find_all(["toll", "mote", "tome", "tote"], 't', "elom");
+        assert_eq!(
+            vec!["mote", "toll"],
+            words_of(&filter_answers(a, None, Some(2)))
+        );
+        assert_eq!(
+            vec!["mote", "toll"],
+            words_of(&filter_answers(b, None, Some(2)))
+        );
+    }
+
+    #[test]
+    fn filter_answers_min_score_and_top() {
+        let answers = find_all(["tote", "motel", "tomtom", "mote"], 't', "elom");
+        assert_eq!(
+            vec!["motel"],
+            words_of(&filter_answers(answers, Some(2), Some(1)))
+        );
+    }
+
+    #[test]
+    fn validate_puzzle_accepts_letters() {
+        assert_eq!(Ok(()), validate_puzzle('t', "elom"));
+        assert_eq!(Ok(()), validate_puzzle('\u{e9}', "caf"));
+    }
+
+    #[test]
+    fn validate_puzzle_rejects_digits() {
+        assert_eq!(
+            Err(PuzzleError::NotAlphabetic('3')),
+            validate_puzzle('t', "el3m")
+        );
+        assert_eq!(
+            Err(PuzzleError::NotAlphabetic('7')),
+            validate_puzzle('7', "elom")
+        );
+    }
+
+    #[test]
+    fn validate_puzzle_rejects_punctuation() {
+        assert_eq!(
+            Err(PuzzleError::NotAlphabetic('\'')),
+            validate_puzzle('t', "el'm")
+        );
+        assert_eq!(
+            Err(PuzzleError::NotAlphabetic('-')),
+            validate_puzzle('-', "elom")
+        );
+    }
+
+    #[test]
+    fn validate_puzzle_rejects_whitespace() {
+        assert_eq!(
+            Err(PuzzleError::NotAlphabetic(' ')),
+            validate_puzzle('t', "el m")
+        );
+        assert_eq!(
+            Err(PuzzleError::NotAlphabetic('\t')),
+            validate_puzzle('t', "elom\t")
+        );
+    }
+
+    #[test]
+    fn validate_puzzle_rejects_required_in_extra() {
+        assert_eq!(
+            Err(PuzzleError::RequiredInExtra('t')),
+            validate_puzzle('t', "telom")
+        );
+    }
+
+    #[test]
+    fn parse_puzzle_splits_required_and_extra() {
+        assert_eq!(Ok(('t', "elomic".to_string())), parse_puzzle("telomic"));
+    }
+
+    #[test]
+    fn parse_puzzle_too_few_letters() {
+        assert_eq!(Err(PuzzleError::WrongLetterCount(0)), parse_puzzle(""));
+        assert_eq!(
+            Err(PuzzleError::WrongLetterCount(6)),
+            parse_puzzle("telomi")
+        );
+    }
+
+    #[test]
+    fn parse_puzzle_too_many_letters() {
+        assert_eq!(
+            Err(PuzzleError::WrongLetterCount(8)),
+            parse_puzzle("telomics")
+        );
+    }
+
+    #[test]
+    fn parse_puzzle_duplicate_letters() {
+        assert_eq!(
+            Err(PuzzleError::RequiredInExtra('t')),
+            parse_puzzle("telomit")
+        );
+        assert_eq!(
+            Err(PuzzleError::DuplicateExtra('o')),
+            parse_puzzle("telomoc")
+        );
+    }
+
+    #[test]
+    fn parse_puzzle_non_letters() {
+        assert_eq!(
+            Err(PuzzleError::NotAlphabetic('1')),
+            parse_puzzle("telom1c")
+        );
+    }
+
+    #[test]
+    fn min_distinct_letters_disabled_by_default() {
+        assert!(check_word("aaaa", 'a', "bcdefg").is_some());
+    }
+
+    #[test]
+    fn min_distinct_letters_rejects_repeated_letters() {
+        let scoring = Scoring {
+            min_distinct_letters: 2,
+            ..Scoring::default()
+        };
+
+        assert_eq!(None, check_word_with("aaaa", 'a', "bcdefg", &scoring));
+        assert!(check_word_with("abab", 'a', "bcdefg", &scoring).is_some());
+        assert!(check_word_with("\u{e9}t\u{e9}t", '\u{e9}', "t", &scoring).is_some());
+    }
+
+    #[test]
+    fn masked_check_min_distinct_letters() {
+        let scoring = Scoring {
+            min_distinct_letters: 3,
+            ..Scoring::default()
+        };
+        let masks = LetterMasks::new('t', "elom").unwrap();
+
+        for w in ["tttt", "tote", "tome", "toot", "motel"] {
+            assert_eq!(
+                check_word_with(w, 't', "elom", &scoring),
+                check_word_masked(w, 't', "elom", &masks, &scoring)
+            );
+        }
+
+        assert_eq!(
+            None,
+            check_word_masked("toot", 't', "elom", &masks, &scoring)
+        );
+        assert!(check_word_masked("tote", 't', "elom", &masks, &scoring).is_some());
+    }
+
+    #[test]
+    fn group_by_length_empty() {
+        assert!(group_by_length(&[]).is_empty());
+    }
+
+    #[test]
+    fn group_by_length_sorts_groups_and_words() {
+        let answers = find_all(
+            ["tote", "motel", "tomtom", "mote", "totem", "caf\u{e9}"],
+            't',
+            "elom",
+        );
+        let groups = group_by_length(&answers);
+
+        assert_eq!(vec![4, 5, 6], groups.keys().copied().collect::<Vec<_>>());
+        assert_eq!(
+            vec!["mote", "tote"],
+            groups[&4]
+                .iter()
+                .map(|a| a.word.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["motel", "totem"],
+            groups[&5]
+                .iter()
+                .map(|a| a.word.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["tomtom"],
+            groups[&6]
+                .iter()
+                .map(|a| a.word.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn two_letter_counts_tallies_prefixes() {
+        let answers = find_all(
+            ["tote", "tome", "totem", "telo", "mote", "motel"],
+            't',
+            "elom",
+        );
+        let counts = two_letter_counts(&answers);
+
+        assert_eq!(3, counts.len());
+        assert_eq!(2, counts["mo"]);
+        assert_eq!(1, counts["te"]);
+        assert_eq!(3, counts["to"]);
+    }
+
+    #[test]
+    fn two_letter_counts_folds_case() {
+        let answers = find_all_ci(["Tote", "tome", "TOTEM"], 't', "elom");
+        assert_eq!(3, two_letter_counts(&answers)["to"]);
+    }
+
+    #[test]
+    fn display_pangram_answer() {
+        let ans = check_word("motel", 't', "elom").unwrap();
+        assert_eq!("* 12 motel", ans.to_string());
+    }
+
+    #[test]
+    fn display_non_pangram_answer() {
+        let ans = check_word("tome", 't', "elom").unwrap();
+        assert_eq!("  1  tome", ans.to_string());
+    }
+
+    fn answer(word: &str, score: i32, is_pangram: bool) -> Answer {
+        Answer {
+            word: word.to_string(),
+            score,
+            is_pangram,
+        }
+    }
+
+    #[test]
+    fn answers_sort_by_descending_score() {
+        let mut answers = vec![
+            answer("tome", 1, false),
+            answer("motel", 12, true),
+            answer("tomtom", 6, false),
+        ];
+        answers.sort();
+        assert_eq!(vec!["motel", "tomtom", "tome"], words_of(&answers));
+    }
+
+    #[test]
+    fn answer_score_ties_put_pangrams_first() {
+        let mut answers = vec![answer("abcdefgh", 8, false), answer("abcdefg", 8, true)];
+        answers.sort();
+        assert_eq!(vec!["abcdefg", "abcdefgh"], words_of(&answers));
+    }
+
+    #[test]
+    fn answer_score_ties_sort_alphabetically() {
+        let mut answers = vec![
+            answer("tote", 1, false),
+            answer("mote", 1, false),
+            answer("tome", 1, false),
+        ];
+        answers.sort();
+        assert_eq!(vec!["mote", "tome", "tote"], words_of(&answers));
+    }
+
+    fn sorted_words(key: SortKey) -> Vec<String> {
+        let mut answers = find_all(
+            ["tote", "motel", "tomtom", "mote", "toll", "lotto", "emotel"],
+            't',
+            "elom",
+        );
+        sort_answers_by(&mut answers, key);
+        answers.into_iter().map(|a| a.word).collect()
+    }
+
+    #[test]
+    fn sort_by_score() {
+        assert_eq!(
+            vec!["emotel", "motel", "tomtom", "lotto", "mote", "toll", "tote"],
+            sorted_words(SortKey::Score)
+        );
+    }
+
+    #[test]
+    fn sort_by_alpha() {
+        assert_eq!(
+            vec!["emotel", "lotto", "mote", "motel", "toll", "tomtom", "tote"],
+            sorted_words(SortKey::Alpha)
+        );
+    }
+
+    #[test]
+    fn sort_by_length() {
+        assert_eq!(
+            vec!["mote", "toll", "tote", "lotto", "motel", "emotel", "tomtom"],
+            sorted_words(SortKey::Length)
+        );
+    }
+
+    fn sorted_words_with(primary: SortKey, tie_break: TieBreak) -> Vec<String> {
+        let mut answers = find_all(
+            ["tote", "motel", "tomtom", "mote", "toll", "lotto", "emotel"],
+            't',
+            "elom",
+        );
+        sort_answers(&mut answers, &SortOptions { primary, tie_break });
+        answers.into_iter().map(|a| a.word).collect()
+    }
+
+    #[test]
+    fn sort_options_score_then_alpha() {
+        assert_eq!(
+            vec!["emotel", "motel", "tomtom", "lotto", "mote", "toll", "tote"],
+            sorted_words_with(SortKey::Score, TieBreak::Alpha)
+        );
+    }
+
+    #[test]
+    fn sort_options_score_then_length_is_stable() {
+        // The one point words all have four letters, so they keep the
+        // dictionary order.
+        assert_eq!(
+            vec!["emotel", "motel", "tomtom", "lotto", "tote", "mote", "toll"],
+            sorted_words_with(SortKey::Score, TieBreak::Length)
+        );
+    }
+
+    #[test]
+    fn sort_options_length_then_pangram() {
+        assert_eq!(
+            vec!["tote", "mote", "toll", "motel", "lotto", "emotel", "tomtom"],
+            sorted_words_with(SortKey::Length, TieBreak::Pangram)
+        );
+    }
+
+    #[test]
+    fn sort_options_length_then_alpha_matches_sort_key() {
+        assert_eq!(
+            sorted_words(SortKey::Length),
+            sorted_words_with(SortKey::Length, TieBreak::Alpha)
+        );
+    }
+
+    #[test]
+    fn default_sort_is_score() {
+        assert_eq!(SortKey::Score, SortKey::default());
+    }
+
+    #[test]
+    fn candidate_words_are_letters() {
+        assert!(is_candidate_word("tote"));
+        assert!(is_candidate_word("Tote"));
+        assert!(is_candidate_word("caf\u{e9}"));
+        assert!(!is_candidate_word(""));
+    }
+
+    #[test]
+    fn candidate_words_reject_apostrophes() {
+        assert!(!is_candidate_word("can't"));
+        assert!(!is_candidate_word("tote's"));
+    }
+
+    #[test]
+    fn candidate_words_reject_hyphens() {
+        assert!(!is_candidate_word("mother-in-law"));
+        assert!(!is_candidate_word("-tote"));
+    }
+
+    #[test]
+    fn candidate_words_reject_digits() {
+        assert!(!is_candidate_word("t0te"));
+        assert!(!is_candidate_word("1984"));
+    }
+
+    #[test]
+    fn max_word_len_unset_by_default() {
+        assert!(check_word("tomtomtomtom", 't', "elom").is_some());
+    }
+
+    #[test]
+    fn max_word_len_rejects_long_words() {
+        let scoring = Scoring {
+            max_word_len: Some(7),
+            ..Scoring::default()
+        };
+        let masks = LetterMasks::new('t', "elom").unwrap();
+
+        assert_eq!(None, check_word_with("tomtomto", 't', "elom", &scoring));
+        assert_eq!(
+            7,
+            check_word_with("tomtomt", 't', "elom", &scoring)
+                .unwrap()
+                .score
+        );
+        assert_eq!(
+            None,
+            check_word_masked("tomtomto", 't', "elom", &masks, &scoring)
+        );
+        assert_eq!(
+            7,
+            check_word_masked("tomtomt", 't', "elom", &masks, &scoring)
+                .unwrap()
+                .score
+        );
+    }
+
+    #[test]
+    fn puzzle_new_accepts_valid_letters() {
+        let puzzle = Puzzle::new('t', "elom").unwrap();
+        assert_eq!('t', puzzle.required());
+        assert_eq!("elom", puzzle.extra());
+    }
+
+    #[test]
+    fn puzzle_new_rejects_invalid_letters() {
+        assert_eq!(
+            Err(PuzzleError::NotAlphabetic('3')),
+            Puzzle::new('t', "el3m")
+        );
+        assert_eq!(Err(PuzzleError::EmptyExtra), Puzzle::new('t', ""));
+        assert_eq!(
+            Err(PuzzleError::RequiredInExtra('t')),
+            Puzzle::new('t', "elomt")
+        );
+        assert_eq!(
+            Err(PuzzleError::DuplicateExtra('l')),
+            Puzzle::new('t', "ellom")
+        );
+    }
+
+    #[test]
+    fn puzzle_check_word_matches_free_function() {
+        let words = ["tote", "vote", "motel", "Tote", "caf\u{e9}", "tot"];
+
+        for (required, extra) in [('t', "elom"), ('c', "af\u{e9}")] {
+            let puzzle = Puzzle::new(required, extra).unwrap();
+
+            for w in words {
+                assert_eq!(check_word(w, required, extra), puzzle.check_word(w));
+            }
+
+            assert_eq!(find_all(words, required, extra), puzzle.find_all(words));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn word_set(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn remove_words_drops_excluded_answers() {
+        let answers = find_all(["tote", "motel", "tome"], 't', "elom");
+        let remaining = remove_words(answers, &word_set(&["tote", "motel"]));
+        assert_eq!(vec!["tome"], words_of(&remaining));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn remove_words_ignores_words_not_in_answers() {
+        let answers = find_all(["tote", "motel"], 't', "elom");
+        let remaining = remove_words(answers, &word_set(&["vote", "Tote", "zebra"]));
+        assert_eq!(vec!["tote", "motel"], words_of(&remaining));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn remove_words_empty_exclude_keeps_everything() {
+        let answers = find_all(["tote", "motel"], 't', "elom");
+        assert_eq!(2, remove_words(answers, &HashSet::new()).len());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn apply_bonuses_adds_to_score() {
+        let mut answers = find_all(["tote", "motel"], 't', "elom");
+        let bonuses: HashMap<String, i32> = [("motel".to_string(), 3)].into_iter().collect();
+        apply_bonuses(&mut answers, &bonuses);
+
+        assert_eq!(1, answers[0].score);
+        assert_eq!(15, answers[1].score);
+        assert!(answers[1].is_pangram);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn apply_bonuses_ignores_unmatched_words() {
+        let mut answers = find_all(["tote", "motel"], 't', "elom");
+        let expected = answers.clone();
+        let bonuses: HashMap<String, i32> = [("zebra".to_string(), 10), ("Tote".to_string(), 2)]
+            .into_iter()
+            .collect();
+        apply_bonuses(&mut answers, &bonuses);
+
+        assert_eq!(expected, answers);
+    }
+
+    #[test]
+    fn best_answer_empty_is_none() {
+        assert_eq!(None, best_answer(&[]));
+    }
+
+    #[test]
+    fn best_answer_picks_highest_score() {
+        let answers = find_all(["tote", "motel", "tome"], 't', "elom");
+        assert_eq!("motel", best_answer(&answers).unwrap().word);
+    }
+
+    #[test]
+    fn best_answer_ties_use_canonical_order() {
+        let answers = find_all(["tote", "tome", "toot"], 't', "elom");
+        assert_eq!("tome", best_answer(&answers).unwrap().word);
+    }
+
+    #[test]
+    fn total_score_sums_answers() {
+        let answers = find_all(["tote", "motel", "tome"], 't', "elom");
+        assert_eq!(14, total_score(&answers));
+        assert_eq!(0, total_score(&[]));
+    }
+
+    #[test]
+    fn score_sequence_empty_has_no_bonus() {
+        assert_eq!(0, score_sequence(&[], 5));
+    }
+
+    #[test]
+    fn score_sequence_adds_bonus_once() {
+        let answers = find_all(["tote", "motel", "tome"], 't', "elom");
+
+        assert_eq!(19, score_sequence(&answers, 5));
+        assert_eq!(total_score(&answers), score_sequence(&answers, 0));
+        assert_eq!(6, score_sequence(&answers[..1], 5));
+    }
+
+    #[test]
+    fn genius_threshold_rounds_up() {
+        // 70% of 14 is 9.8.
+        let answers = find_all(["tote", "motel", "tome"], 't', "elom");
+        assert_eq!(10, genius_threshold(&answers));
+    }
+
+    #[test]
+    fn genius_threshold_exact_percentage() {
+        // 70% of 10 is exactly 7.
+        let answers = find_all(["lotto", "motto"], 't', "elom");
+        assert_eq!(10, total_score(&answers));
+        assert_eq!(7, genius_threshold(&answers));
+        assert_eq!(0, genius_threshold(&[]));
+    }
+
+    #[test]
+    fn check_word_opt_without_required_letter() {
+        assert!(check_word_opt("pull", None, "unrlap").is_some());
+        assert!(check_word_opt("loon", None, "unrlap").is_none());
+        assert!(check_word_opt("pul", None, "unrlap").is_none());
+    }
+
+    #[test]
+    fn check_word_opt_with_required_letter_matches_check_word() {
+        for word in ["loon", "pronoun", "unpopular", "pull", "lop"] {
+            assert_eq!(
+                check_word(word, 'o', "unrlap"),
+                check_word_opt(word, Some('o'), "unrlap")
+            );
+        }
+    }
+
+    #[test]
+    fn check_word_opt_pangram_uses_only_extra_letters() {
+        let answer = check_word_opt("plural", None, "plura").unwrap();
+        assert!(answer.is_pangram);
+        assert_eq!(6 + PANGRAM_BONUS, answer.score);
+
+        assert!(!check_word_opt("pull", None, "plura").unwrap().is_pangram);
+    }
+
+    #[test]
+    fn pangrams_returns_only_pangrams() {
+        let answers = find_all(["tote", "motel", "tome", "omelet"], 't', "elom");
+        let found: Vec<&str> = pangrams(&answers).iter().map(|a| a.word.as_str()).collect();
+        assert_eq!(vec!["motel", "omelet"], found);
+    }
+
+    #[test]
+    fn pangrams_empty_when_none_found() {
+        let answers = find_all(["tote", "tome"], 't', "elom");
+        assert!(pangrams(&answers).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn crlf_dictionary_finds_answers() {
+        let dict = b"tote\r\nvote\r\nmotel\r\n";
+        let answers = find_all_with_dict(&dict[..], 't', "elom", false).unwrap();
+        assert_eq!(vec!["tote", "motel"], words_of(&answers));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn non_letter_words_are_skipped() {
+        let dict = b"tote\ntote's\nmo-tel\nmotel\n";
+        let answers = find_all_with_dict(&dict[..], 't', "elom", false).unwrap();
+        assert_eq!(vec!["tote", "motel"], words_of(&answers));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn surrounding_whitespace_is_trimmed() {
+        let dict = b"  tote\t\n motel \n";
+        let answers = find_all_with_dict(&dict[..], 't', "elom", false).unwrap();
+        assert_eq!(vec!["tote", "motel"], words_of(&answers));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn invalid_utf8_line_is_a_decode_error() {
+        let dict = b"tote\nmotel\nmo\xfftel\ntome\n";
+        let result = find_all_with_dict(&dict[..], 't', "elom", false);
+
+        match result {
+            Err(Error::Decode(line, _)) => assert_eq!(3, line),
+            other => panic!("expected a decode error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn decode_error_line_counts_skipped_lines() {
+        let dict = b"tote's\n\nzebra\n\xff\n";
+        let result = find_all_with_dict(&dict[..], 't', "elom", false);
+
+        match result {
+            Err(Error::Decode(line, _)) => assert_eq!(4, line),
+            other => panic!("expected a decode error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn display_io_error() {
+        let err = Error::from(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+        assert_eq!("no such file", err.to_string());
+    }
+
+    #[test]
+    fn display_puzzle_error() {
+        let err = Error::from(PuzzleError::DuplicateExtra('e'));
+        assert_eq!(
+            "invalid puzzle: extra letter 'e' is listed more than once",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn display_decode_error() {
+        let err = Error::Decode(3, "stream did not contain valid UTF-8".to_string());
+        assert_eq!(
+            "line 3: stream did not contain valid UTF-8",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn solve_from_text_finds_answers() {
+        let answers = solve_from_text("tote\nvote\nmotel\nsoapy\ntome\n", 't', "elom");
+        assert_eq!(vec!["tote", "motel", "tome"], words_of(&answers));
+    }
+
+    #[test]
+    fn solve_from_text_trims_whitespace_and_cr() {
+        let answers = solve_from_text("  tote\r\n\tmotel \r\n\n  \ntome", 't', "elom");
+        assert_eq!(vec!["tote", "motel", "tome"], words_of(&answers));
+    }
+
+    #[test]
+    fn solve_from_text_empty_dictionary() {
+        assert!(solve_from_text("", 't', "elom").is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn answers_json_round_trip() {
+        let answers = find_all(["tote", "motel", "tome"], 't', "elom");
+        assert!(answers.iter().any(|a| a.is_pangram));
+
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(answers, answers_from_json(&json).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn answers_from_invalid_json_is_an_error() {
+        let result = answers_from_json(r#"[{"word":"tote"}]"#);
+        assert!(matches!(result, Err(Error::Json(_))));
+    }
+
+    #[test]
+    fn check_word_trims_leading_whitespace() {
+        assert_eq!("tote", check_word("  tote", 't', "elom").unwrap().word);
+    }
+
+    #[test]
+    fn check_word_trims_trailing_whitespace() {
+        let answer = check_word("motel \t\r\n", 't', "elom").unwrap();
+        assert_eq!("motel", answer.word);
+        assert_eq!(12, answer.score);
+    }
+
+    #[test]
+    fn check_word_rejects_interior_whitespace() {
+        assert_eq!(None, check_word("to te", 't', "elom"));
+        assert_eq!(None, Puzzle::new('t', "elom").unwrap().check_word("mo tel"));
+    }
+
+    #[test]
+    fn masked_check_trims_whitespace() {
+        let puzzle = Puzzle::new('t', "elom").unwrap();
+        assert_eq!(
+            check_word(" motel ", 't', "elom"),
+            puzzle.check_word(" motel ")
+        );
+    }
+
+    #[test]
+    fn length_histogram_counts_lengths() {
+        let answers = find_all(["tote", "tome", "motel", "toot", "omelet"], 't', "elom");
+        let histogram = length_histogram(&answers);
+
+        assert_eq!(
+            vec![(4, 3), (5, 1), (6, 1)],
+            histogram.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn length_histogram_uses_char_length() {
+        let answers = find_all(["café", "cafés"], 'c', "afés");
+        let histogram = length_histogram(&answers);
+
+        assert_eq!(Some(&1), histogram.get(&4));
+        assert_eq!(Some(&1), histogram.get(&5));
+    }
+
+    #[test]
+    fn length_histogram_empty() {
+        assert!(length_histogram(&[]).is_empty());
+    }
+
+    #[test]
+    fn min_required_occurrences_defaults_to_one() {
+        assert_eq!(1, Scoring::default().min_required_occurrences);
+        assert!(check_word("tome", 't', "elom").is_some());
+    }
+
+    #[test]
+    fn min_required_occurrences_rejects_single_required_letter() {
+        let scoring = Scoring {
+            min_required_occurrences: 2,
+            ..Scoring::default()
+        };
+
+        assert_eq!(None, check_word_with("tome", 't', "elom", &scoring));
+        assert!(check_word_with("tote", 't', "elom", &scoring).is_some());
+    }
+
+    #[test]
+    fn masked_check_min_required_occurrences() {
+        let scoring = Scoring {
+            min_required_occurrences: 2,
+            ..Scoring::default()
+        };
+        let puzzle = Puzzle::new('t', "elom").unwrap();
+
+        for word in ["tome", "tote", "motel", "tottle", "moot"] {
+            assert_eq!(
+                check_word_with(word, 't', "elom", &scoring),
+                puzzle.check_word_with(word, &scoring),
+            );
+        }
+    }
+
+    #[test]
+    fn min_required_occurrences_zero_still_needs_required_letter() {
+        let scoring = Scoring {
+            min_required_occurrences: 0,
+            ..Scoring::default()
+        };
+
+        assert_eq!(None, check_word_with("mole", 't', "elom", &scoring));
+    }
+
+    #[test]
+    fn find_all_slice_of_str() {
+        let words: &[&str] = &["tote", "vote", "motel"];
+        assert_eq!(
+            vec!["tote", "motel"],
+            words_of(&find_all_slice(words, 't', "elom"))
+        );
+    }
+
+    #[test]
+    fn find_all_slice_of_string() {
+        let words: Vec<String> = vec!["tote".into(), "vote".into(), "motel".into()];
+        assert_eq!(
+            vec!["tote", "motel"],
+            words_of(&find_all_slice(&words, 't', "elom"))
+        );
+    }
+
+    #[test]
+    fn score_breakdown_four_letter_word() {
+        let answer = check_word("tome", 't', "elom").unwrap();
+        let expected = ScoreBreakdown {
+            base: 1,
+            pangram_bonus: 0,
+            letter_bonus: 0,
+            total: 1,
+        };
+
+        assert_eq!(expected, score_breakdown(&answer, &Scoring::default()));
+    }
+
+    #[test]
+    fn score_breakdown_long_word() {
+        let answer = check_word("pronoun", 'o', "unrlap").unwrap();
+        let expected = ScoreBreakdown {
+            base: 7,
+            pangram_bonus: 0,
+            letter_bonus: 0,
+            total: 7,
+        };
+
+        assert_eq!(expected, score_breakdown(&answer, &Scoring::default()));
+    }
+
+    #[test]
+    fn score_breakdown_pangram() {
+        let answer = check_word("motel", 't', "elom").unwrap();
+        let expected = ScoreBreakdown {
+            base: 5,
+            pangram_bonus: PANGRAM_BONUS,
+            letter_bonus: 0,
+            total: answer.score,
+        };
+
+        assert_eq!(expected, score_breakdown(&answer, &Scoring::default()));
+    }
+
+    #[test]
+    fn pangram_with_eight_letter_puzzle() {
+        let answer = check_word("flamingo", 'g', "flamino").unwrap();
+        assert!(answer.is_pangram);
+        assert_eq!(8 + PANGRAM_BONUS, answer.score);
+
+        assert!(!check_word("gloom", 'g', "flamino").unwrap().is_pangram);
+    }
+
+    #[test]
+    fn pangram_with_nine_letter_puzzle() {
+        let answer = check_word("coeducation", 'e', "ducation").unwrap();
+        assert!(answer.is_pangram);
+        assert_eq!(11 + PANGRAM_BONUS, answer.score);
+
+        assert_eq!(None, check_word("eductions", 'e', "ducation"));
+        assert!(!check_word("donate", 'e', "ducation").unwrap().is_pangram);
+    }
+
+    #[test]
+    fn masked_check_matches_scalar_for_large_puzzles() {
+        let puzzles = [
+            ('g', "flamino"),
+            ('e', "ducation"),
+            ('e', "abcdfghijklmnopqrstuvwxyz"),
+            ('e', "abcdfghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+        ];
+        let words = [
+            "flamingo",
+            "coeducation",
+            "education",
+            "donate",
+            "zebra",
+            "Zebra",
+        ];
+
+        for (required, extra) in puzzles {
+            let puzzle = Puzzle::new(required, extra).unwrap();
+
+            for word in words {
+                assert_eq!(check_word(word, required, extra), puzzle.check_word(word));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_dict_skips_non_letter_words() {
+        let words = read_dict(&b"tote\r\n  motel\ntote's\n\nvote\n"[..]).unwrap();
+        assert_eq!(vec!["tote", "motel", "vote"], words);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn read_dict_reports_decode_line() {
+        let result = read_dict(&b"tote\n\xff\n"[..]);
+        assert!(matches!(result, Err(Error::Decode(2, _))));
+    }
+
+    const INDEX_WORDS: [&str; 14] = [
+        "tote",
+        "vote",
+        "motel",
+        "soapy",
+        "tome",
+        "Tome",
+        "loon",
+        "pronoun",
+        "unpopular",
+        "café",
+        "cafés",
+        "face",
+        "  roll ",
+        "mo tel",
+    ];
+
+    #[test]
+    fn dictionary_index_matches_find_all() {
+        let index = DictionaryIndex::new(INDEX_WORDS);
+        let puzzles = [
+            ('t', "elom"),
+            ('o', "unrlap"),
+            ('c', "afés"),
+            ('T', "ome"),
+            ('v', "elomt"),
+        ];
+
+        for (required, extra) in puzzles {
+            assert_eq!(
+                find_all(INDEX_WORDS, required, extra),
+                index.find_all(required, extra)
+            );
+        }
+    }
+
+    #[test]
+    fn find_all_indexed_matches_find_all() {
+        let puzzles = [('t', "elom"), ('o', "unrlap"), ('c', "afés"), ('T', "ome")];
+
+        for (required, extra) in puzzles {
+            let (answers, index) = find_all_indexed(INDEX_WORDS, required, extra);
+
+            assert_eq!(find_all(INDEX_WORDS, required, extra), answers);
+            assert_eq!(INDEX_WORDS.len(), index.len());
+        }
+    }
+
+    #[test]
+    fn dictionary_index_solve_with_scoring() {
+        let index: DictionaryIndex = INDEX_WORDS.iter().collect();
+        let puzzle = Puzzle::new('o', "unrlap").unwrap();
+        let scoring = Scoring {
+            min_word_len: 7,
+            ..Scoring::default()
+        };
+
+        assert_eq!(puzzle.find_all(INDEX_WORDS), index.solve(&puzzle));
+        assert_eq!(
+            vec!["pronoun", "unpopular"],
+            words_of(&index.solve_with(&puzzle, &scoring))
+        );
+    }
+
+    #[test]
+    fn dictionary_index_len_and_words() {
+        let index = DictionaryIndex::new(["tote", " motel "]);
+
+        assert_eq!(2, index.len());
+        assert!(!index.is_empty());
+        assert_eq!(vec!["tote", "motel"], index.words().collect::<Vec<_>>());
+        assert!(DictionaryIndex::default().is_empty());
+    }
+
+    /// Get a path in the temp directory that is unique to this test run.
+    #[cfg(feature = "serde")]
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("spellingbee-lib-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn dictionary_index_save_and_load() {
+        let source = temp_path("index-source.txt");
+        let path = temp_path("index-saved.json");
+        std::fs::write(&source, INDEX_WORDS.join("\n")).unwrap();
+
+        let index = DictionaryIndex::new(INDEX_WORDS);
+        index.save(&path, &source).unwrap();
+        let loaded = DictionaryIndex::load(&path, &source).unwrap().unwrap();
+
+        assert_eq!(
+            index.words().collect::<Vec<_>>(),
+            loaded.words().collect::<Vec<_>>()
+        );
+
+        for (required, extra) in [('t', "elom"), ('o', "unrlap"), ('c', "afés")] {
+            assert_eq!(
+                index.find_all(required, extra),
+                loaded.find_all(required, extra)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn dictionary_index_stale_after_source_changes() {
+        let source = temp_path("stale-source.txt");
+        let path = temp_path("stale-saved.json");
+        std::fs::write(&source, "tote\nmotel\n").unwrap();
+
+        DictionaryIndex::new(["tote", "motel"])
+            .save(&path, &source)
+            .unwrap();
+        assert!(DictionaryIndex::load(&path, &source).unwrap().is_some());
+
+        // Set the modification time explicitly since rewriting the file right
+        // away might not change it on file systems with coarse timestamps.
+        let file = std::fs::File::options().append(true).open(&source).unwrap();
+        file.set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(86_400))
+            .unwrap();
+
+        assert!(DictionaryIndex::load(&path, &source).unwrap().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn dictionary_index_load_missing_file_is_error() {
+        let source = temp_path("missing-source.txt");
+        std::fs::write(&source, "tote\n").unwrap();
+
+        assert!(matches!(
+            DictionaryIndex::load(temp_path("missing.json"), &source),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn wildcard_covers_one_missing_letter() {
+        let answer = check_word_wild("polar", 'o', "unrapy", Some('y')).unwrap();
+        assert_eq!("polar", answer.word);
+        assert_eq!(5, answer.score);
+        assert!(!answer.is_pangram);
+    }
+
+    #[test]
+    fn wildcard_cannot_cover_two_missing_letters() {
+        // Both 'l' and 'c' are missing from the puzzle.
+        assert_eq!(None, check_word_wild("clown", 'o', "unrapy", Some('y')));
+        // Even the same missing letter twice needs the wildcard twice.
+        assert_eq!(None, check_word_wild("poll", 'o', "unrapy", Some('y')));
+    }
+
+    #[test]
+    fn wildcard_matches_itself() {
+        assert!(check_word_wild("pony", 'o', "unrapy", Some('y')).is_some());
+    }
+
+    #[test]
+    fn wildcard_does_not_replace_required_letter() {
+        assert_eq!(None, check_word_wild("pray", 'o', "unrapy", Some('y')));
+    }
+
+    #[test]
+    fn wildcard_cannot_cover_forbidden_letter() {
+        assert!(check_word_forbidden("polar", 'o', "unrapy", Some('y'), "").is_some());
+        assert_eq!(
+            None,
+            check_word_forbidden("polar", 'o', "unrapy", Some('y'), "l")
+        );
+    }
+
+    #[test]
+    fn forbidden_letter_rejects_puzzle_letters() {
+        // Forbidding a puzzle letter (or the wildcard itself) still rejects the
+        // word.
+        assert_eq!(
+            None,
+            check_word_forbidden("pony", 'o', "unrapy", Some('y'), "y")
+        );
+        assert_eq!(None, check_word_forbidden("loon", 'o', "unrlap", None, "n"));
+        assert!(check_word_forbidden("loon", 'o', "unrlap", None, "p").is_some());
+    }
+
+    #[test]
+    fn no_wildcard_matches_check_word() {
+        for word in ["loon", "pronoun", "unpopular", "polar", "clown"] {
+            assert_eq!(
+                check_word(word, 'o', "unrlap"),
+                check_word_wild(word, 'o', "unrlap", None)
+            );
+        }
+    }
+
+    #[test]
+    fn check_word_ref_borrows_word() {
+        let dictionary = String::from("tote\nmotel\n");
+        let answer = check_word_ref(&dictionary[5..10], 't', "elom").unwrap();
+
+        assert!(std::ptr::eq(&dictionary[5..10], answer.word));
+        assert!(answer.is_pangram);
+    }
+
+    #[test]
+    fn check_word_ref_matches_check_word() {
+        for word in ["tote", " motel ", "vote", "tom", "omelet"] {
+            assert_eq!(
+                check_word(word, 't', "elom"),
+                check_word_ref(word, 't', "elom").map(|a| a.to_owned())
+            );
+        }
+    }
+
+    #[test]
+    fn answer_ref_to_owned_copies_fields() {
+        let answer = AnswerRef {
+            word: "motel",
+            score: 12,
+            is_pangram: true,
+        };
+        let expected = Answer {
+            word: "motel".to_string(),
+            score: 12,
+            is_pangram: true,
+        };
+
+        assert_eq!(expected, answer.to_owned());
+    }
+
+    #[test]
+    fn pangram_bonus_defaults_to_flat() {
+        assert_eq!(
+            PangramBonus::Flat(PANGRAM_BONUS),
+            Scoring::default().pangram_bonus_mode
+        );
+    }
+
+    #[test]
+    fn per_letter_pangram_bonus() {
+        let scoring = Scoring {
+            pangram_bonus_mode: PangramBonus::PerLetter(2),
+            ..Scoring::default()
+        };
+
+        // A seven letter puzzle gives 2 points for each of the 7 letters.
+        let answer = check_word_with("unpopular", 'o', "unrlap", &scoring).unwrap();
+        assert!(answer.is_pangram);
+        assert_eq!(9 + 14, answer.score);
+
+        // Words that are not pangrams get no bonus.
+        assert_eq!(
+            7,
+            check_word_with("pronoun", 'o', "unrlap", &scoring)
+                .unwrap()
+                .score
+        );
+    }
+
+    #[test]
+    fn per_letter_pangram_bonus_masked_matches_scalar() {
+        let scoring = Scoring {
+            pangram_bonus_mode: PangramBonus::PerLetter(3),
+            ..Scoring::default()
+        };
+        let puzzle = Puzzle::new('o', "unrlap").unwrap();
+
+        for word in ["unpopular", "populous", "pronoun", "loon"] {
+            assert_eq!(
+                check_word_with(word, 'o', "unrlap", &scoring),
+                puzzle.check_word_with(word, &scoring)
+            );
+        }
+    }
+
+    #[test]
+    fn per_letter_pangram_bonus_breakdown() {
+        let scoring = Scoring {
+            pangram_bonus_mode: PangramBonus::PerLetter(2),
+            ..Scoring::default()
+        };
+        let answer = check_word_with("unpopular", 'o', "unrlap", &scoring).unwrap();
+        let breakdown = score_breakdown(&answer, &scoring);
+
+        assert_eq!(14, breakdown.pangram_bonus);
+        assert_eq!(answer.score, breakdown.total);
+    }
+
+    #[test]
+    fn find_all_sorted_uses_canonical_order() {
+        let words = ["tote", "omelet", "lotto", "motel", "tome", "toot", "motto"];
+        let answers = find_all_sorted(words, 't', "elom");
+
+        assert_eq!(
+            vec!["omelet", "motel", "lotto", "motto", "tome", "toot", "tote"],
+            words_of(&answers)
+        );
+    }
+
+    #[test]
+    fn filter_prefix_keeps_matching_answers() {
+        let answers = find_all(["tote", "motel", "tome", "toot"], 't', "elom");
+        let found: Vec<&str> = filter_prefix(&answers, "to")
+            .iter()
+            .map(|a| a.word.as_str())
+            .collect();
+
+        assert_eq!(vec!["tote", "tome", "toot"], found);
+    }
+
+    #[test]
+    fn filter_prefix_empty_matches_all() {
+        let answers = find_all(["tote", "motel"], 't', "elom");
+        assert_eq!(2, filter_prefix(&answers, "").len());
+    }
+
+    #[test]
+    fn filter_prefix_matching_none() {
+        let answers = find_all(["tote", "motel"], 't', "elom");
+        assert!(filter_prefix(&answers, "zz").is_empty());
+        assert!(filter_prefix(&answers, "TO").is_empty());
+    }
+
+    #[test]
+    fn filter_prefix_ci_ignores_case() {
+        let answers = find_all_ci(["Tote", "motel", "TOME"], 't', "elom");
+
+        let found: Vec<&str> = filter_prefix_ci(&answers, "tO")
+            .iter()
+            .map(|a| a.word.as_str())
+            .collect();
+
+        assert_eq!(vec!["Tote", "TOME"], found);
+        assert!(filter_prefix_ci(&answers, "x").is_empty());
+    }
+
+    #[test]
+    fn filter_length_keeps_exact_length() {
+        let answers = find_all(["tote", "motel", "tomtom", "tome", "lotto"], 't', "elom");
+        let found = |len| -> Vec<&str> {
+            filter_length(&answers, len)
+                .iter()
+                .map(|a| a.word.as_str())
+                .collect()
+        };
+
+        assert_eq!(vec!["tote", "tome"], found(4));
+        assert_eq!(vec!["motel", "lotto"], found(5));
+        assert_eq!(vec!["tomtom"], found(6));
+    }
+
+    #[test]
+    fn filter_length_counts_chars() {
+        let answers = find_all(["t\u{e9}t\u{e9}"], 't', "\u{e9}");
+        assert_eq!(1, filter_length(&answers, 4).len());
+    }
+
+    #[test]
+    fn filter_length_matching_none() {
+        let answers = find_all(["tote", "motel"], 't', "elom");
+        assert!(filter_length(&answers, 7).is_empty());
+        assert!(filter_length(&answers, 0).is_empty());
+    }
+
+    #[test]
+    fn count_char_zero_one_and_two() {
+        assert_eq!(0, count_char("mole", 't'));
+        assert_eq!(1, count_char("tome", 't'));
+        assert_eq!(2, count_char("tote", 't'));
+    }
+
+    #[test]
+    fn count_char_is_case_sensitive() {
+        assert_eq!(1, count_char("Tote", 't'));
+    }
+
+    #[test]
+    fn count_char_counts_unicode_scalars() {
+        assert_eq!(2, count_char("éclairé", 'é'));
+        assert_eq!(0, count_char("eclaire\u{301}", 'é'));
+    }
+
+    #[test]
+    fn words_to_reach_target_already_met() {
+        let answers = find_all(["tote", "motel", "tome"], 't', "elom");
+        assert!(words_to_reach(&answers, 10, 10).is_empty());
+        assert!(words_to_reach(&answers, 11, 10).is_empty());
+    }
+
+    #[test]
+    fn words_to_reach_takes_highest_scores_first() {
+        let answers = find_all(["tote", "lotto", "motel", "tome", "motto"], 't', "elom");
+        let needed: Vec<&str> = words_to_reach(&answers, 1, 19)
+            .iter()
+            .map(|a| a.word.as_str())
+            .collect();
+
+        // 1 + 12 (motel) + 5 (lotto) + 5 (motto) = 23 crosses 19.
+        assert_eq!(vec!["motel", "lotto", "motto"], needed);
+    }
+
+    #[test]
+    fn words_to_reach_unreachable_target_takes_everything() {
+        let answers = find_all(["tote", "tome"], 't', "elom");
+        assert_eq!(2, words_to_reach(&answers, 0, 100).len());
+    }
+
+    #[test]
+    fn group_anagrams_buckets_pairs() {
+        let answers = find_all(["tome", "tote", "mote", "motel", "toet"], 't', "elom");
+        let groups: Vec<Vec<&str>> = group_anagrams(&answers)
+            .iter()
+            .map(|g| g.iter().map(|a| a.word.as_str()).collect())
+            .collect();
+
+        assert_eq!(
+            vec![vec!["tome", "mote"], vec!["tote", "toet"], vec!["motel"]],
+            groups
+        );
+    }
+
+    #[test]
+    fn group_anagrams_ignores_case() {
+        let answers = find_all_ci(["Tome", "mote"], 't', "elom");
+        assert_eq!(1, group_anagrams(&answers).len());
+    }
+
+    #[test]
+    fn group_anagrams_unicode_letters() {
+        let answers = find_all(["café", "féca", "face"], 'c', "aféer");
+        let groups = group_anagrams(&answers);
+
+        assert_eq!(2, groups[0].len());
+        assert_eq!("face", groups[1][0].word);
+    }
+
+    #[test]
+    fn group_anagrams_empty() {
+        assert!(group_anagrams(&[]).is_empty());
+    }
+
+    #[test]
+    fn letter_set_contains_puzzle_letters() {
+        let letters = LetterSet::new('t', "elom");
+
+        for c in "telom".chars() {
+            assert!(letters.contains(c));
+        }
+
+        assert!(!letters.contains('a'));
+        assert!(!letters.contains('T'));
+        assert!(!letters.contains('\u{e9}'));
+    }
+
+    #[test]
+    fn letter_set_contains_non_ascii_letters() {
+        let letters = LetterSet::new('\u{e9}', "cafr");
+
+        assert!(letters.contains('\u{e9}'));
+        assert!(letters.contains('c'));
+        assert!(!letters.contains('e'));
+    }
+
+    #[test]
+    fn letter_set_pangram_size() {
+        assert_eq!(5, LetterSet::new('t', "elom").pangram_size());
+        assert_eq!(7, LetterSet::new('o', "unrlap").pangram_size());
+        assert_eq!(5, LetterSet::new('\u{e9}', "cafr").pangram_size());
+    }
+
+    #[test]
+    fn letter_set_pangram_size_ignores_duplicates() {
+        assert_eq!(3, LetterSet::new('t', "eot").pangram_size());
+        assert_eq!(3, LetterSet::new('\u{e9}', "aa\u{e9}c").pangram_size());
+    }
+
+    #[test]
+    fn check_word_set_matches_check_word() {
+        let words = ["tote", "motel", "toot", "tom", "mote\u{e9}", "lemon"];
+
+        for (required, extra) in [('t', "elom"), ('\u{e9}', "motel")] {
+            let letters = LetterSet::new(required, extra);
+
+            for w in words {
+                assert_eq!(check_word(w, required, extra), check_word_set(w, &letters));
+            }
+        }
+    }
+
+    #[test]
+    fn missing_for_pangram_two_letters() {
+        assert_eq!(vec!['e', 'l'], missing_for_pangram("tomtom", 't', "elom"));
+    }
+
+    #[test]
+    fn missing_for_pangram_includes_required_letter() {
+        assert_eq!(vec!['t', 'e'], missing_for_pangram("loom", 't', "elom"));
+    }
+
+    #[test]
+    fn missing_for_pangram_of_pangram_is_empty() {
+        assert!(missing_for_pangram("motel", 't', "elom").is_empty());
+    }
+
+    #[test]
+    fn missing_for_pangram_ignores_letters_not_in_puzzle() {
+        assert_eq!(vec!['l'], missing_for_pangram("remote", 't', "elom"));
+        assert!(missing_for_pangram("motels", 't', "elom").is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn dictionary_stats_mixed_content() {
+        let dict: &[u8] =
+            b"tote\r\n\nmother-in-law\ncaf\xc3\xa9s\n\xff\xfe\ncan't\n  \nmotels\nbe\n";
+        let stats = dictionary_stats(dict).unwrap();
+
+        assert_eq!(9, stats.lines);
+        assert_eq!(2, stats.blank_lines);
+        assert_eq!(vec![5], stats.invalid_utf8_lines);
+        assert_eq!(2, stats.non_letter_words);
+        assert_eq!(Some("be".to_string()), stats.shortest);
+        assert_eq!(Some("motels".to_string()), stats.longest);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn dictionary_stats_ties_keep_first_word() {
+        let stats = dictionary_stats("tote\ntome\nmotel\nvotes\n".as_bytes()).unwrap();
+
+        assert_eq!(Some("tote".to_string()), stats.shortest);
+        assert_eq!(Some("motel".to_string()), stats.longest);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn dictionary_stats_uses_char_length() {
+        let stats = dictionary_stats("caf\u{e9}\ncafes\n".as_bytes()).unwrap();
+        assert_eq!(Some("caf\u{e9}".to_string()), stats.shortest);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn dictionary_stats_empty() {
+        assert_eq!(
+            DictStats::default(),
+            dictionary_stats("".as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn dictionary_stats_returns_read_errors() {
+        // Reads a couple of words and then fails, like a truncated file.
+        struct Truncated(bool);
+
+        impl io::Read for Truncated {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0 {
+                    return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "truncated"));
+                }
+
+                self.0 = true;
+                let words = b"tote\nmotel\n";
+                buf[..words.len()].copy_from_slice(words);
+                Ok(words.len())
+            }
+        }
+
+        let result = dictionary_stats(io::BufReader::new(Truncated(false)));
+        assert_eq!(io::ErrorKind::UnexpectedEof, result.unwrap_err().kind());
+    }
+
+    /// Build `count` answers that are each `len` letters long, the first
+    /// `pangram_count` of which are pangrams.
+    fn answer_set(count: usize, len: usize, pangram_count: usize) -> Vec<Answer> {
+        (0..count)
+            .map(|i| Answer {
+                word: "a".repeat(len),
+                score: len as i32,
+                is_pangram: i < pangram_count,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn difficulty_easy() {
+        assert_eq!(Difficulty::Easy, difficulty(&answer_set(40, 5, 2)));
+    }
+
+    #[test]
+    fn difficulty_medium() {
+        assert_eq!(Difficulty::Medium, difficulty(&answer_set(40, 5, 1)));
+        assert_eq!(Difficulty::Medium, difficulty(&answer_set(10, 5, 3)));
+        assert_eq!(Difficulty::Medium, difficulty(&answer_set(40, 7, 3)));
+    }
+
+    #[test]
+    fn difficulty_hard() {
+        assert_eq!(Difficulty::Hard, difficulty(&answer_set(10, 5, 1)));
+        assert_eq!(Difficulty::Hard, difficulty(&answer_set(10, 7, 1)));
+        assert_eq!(Difficulty::Hard, difficulty(&[]));
+    }
+
+    #[test]
+    fn difficulty_answer_count_boundary() {
+        let count = DIFFICULTY_FEW_ANSWERS;
+        assert_eq!(Difficulty::Easy, difficulty(&answer_set(count, 5, 2)));
+        assert_eq!(Difficulty::Medium, difficulty(&answer_set(count - 1, 5, 2)));
+    }
+
+    #[test]
+    fn difficulty_pangram_boundary() {
+        let pangrams = DIFFICULTY_FEW_PANGRAMS;
+        assert_eq!(
+            Difficulty::Easy,
+            difficulty(&answer_set(40, 5, pangrams + 1))
+        );
+        assert_eq!(Difficulty::Medium, difficulty(&answer_set(40, 5, pangrams)));
+    }
+
+    #[test]
+    fn difficulty_average_length_boundary() {
+        let mut answers = answer_set(39, DIFFICULTY_LONG_AVERAGE_LEN, 2);
+        answers.push(answer_set(1, DIFFICULTY_LONG_AVERAGE_LEN - 1, 0).remove(0));
+        assert_eq!(Difficulty::Easy, difficulty(&answers));
+
+        let answers = answer_set(40, DIFFICULTY_LONG_AVERAGE_LEN, 2);
+        assert_eq!(Difficulty::Medium, difficulty(&answers));
+    }
+
+    #[test]
+    fn difficulty_display() {
+        assert_eq!("easy", Difficulty::Easy.to_string());
+        assert_eq!("hard", Difficulty::Hard.to_string());
+    }
+
+    #[test]
+    fn solve_all_centers_counts_answers_per_letter() {
+        let words = ["tote", "tome", "motel", "mole", "loom", "meet", "toll"];
+        let answers = solve_all_centers(words, "telom");
+
+        let counts: Vec<(char, usize)> = answers.iter().map(|(&c, a)| (c, a.len())).collect();
+        assert_eq!(
+            vec![('e', 5), ('l', 4), ('m', 5), ('o', 6), ('t', 5)],
+            counts
+        );
+    }
+
+    #[test]
+    fn solve_all_centers_matches_find_all() {
+        let words = ["tote", "tome", "motel", "mole", "loom"];
+
+        for (c, answers) in solve_all_centers(words, "telom") {
+            let extra: String = "telom".chars().filter(|&e| e != c).collect();
+            assert_eq!(find_all(words, c, &extra), answers);
+        }
+    }
+
+    #[test]
+    fn solve_all_centers_includes_letters_without_answers() {
+        let answers = solve_all_centers(["tote"], "telom");
+
+        assert_eq!(5, answers.len());
+        assert!(answers[&'m'].is_empty());
+    }
+
+    #[test]
+    fn near_misses_valid_word_is_zero() {
+        assert_eq!(Some(0), near_misses("motel", 't', "elom"));
+        assert_eq!(Some(0), near_misses(" tote ", 't', "elom"));
+    }
+
+    #[test]
+    fn near_misses_one_disallowed_letter() {
+        assert_eq!(Some(1), near_misses("vote", 't', "elom"));
+        assert_eq!(Some(1), near_misses("motels", 't', "elom"));
+    }
+
+    #[test]
+    fn near_misses_only_missing_required_letter() {
+        assert_eq!(Some(1), near_misses("mole", 't', "elom"));
+    }
+
+    #[test]
+    fn near_misses_missing_required_and_disallowed_letter() {
+        assert_eq!(None, near_misses("move", 't', "elom"));
+    }
+
+    #[test]
+    fn near_misses_far_off_words() {
+        assert_eq!(None, near_misses("votes", 't', "elom"));
+        assert_eq!(None, near_misses("soapy", 't', "elom"));
+    }
+
+    #[test]
+    fn near_misses_counts_repeated_disallowed_letters() {
+        assert_eq!(None, near_misses("tamtam", 't', "elom"));
+    }
+
+    #[test]
+    fn near_misses_short_words_are_not_close() {
+        assert_eq!(None, near_misses("tom", 't', "elom"));
+        assert_eq!(None, near_misses("toe", 't', "elom"));
+    }
+
+    #[test]
+    #[cfg(feature = "bundled-dict")]
+    fn find_all_default_uses_bundled_words() {
+        let answers = find_all_default('o', "unrlap");
+
+        assert!(!answers.is_empty());
+        assert_eq!(solve_from_text(BUNDLED_WORDS, 'o', "unrlap"), answers);
+        assert!(answers
+            .iter()
+            .all(|a| BUNDLED_WORDS.lines().any(|w| w == a.word)));
+    }
+
+    #[test]
+    fn for_each_answer_sums_scores() {
+        let mut total = 0;
+        let mut pangrams = 0;
+
+        for_each_answer(["tote", "motel", "vote", "tome"], 't', "elom", |ans| {
+            total += ans.score;
+            pangrams += usize::from(ans.is_pangram);
+        });
+
+        assert_eq!(14, total);
+        assert_eq!(1, pangrams);
+    }
+
+    #[test]
+    fn find_all_results_matches_find_all() {
+        let words = ["tote", "vote", "motel", "tome"];
+
+        assert_eq!(
+            Ok::<_, ()>(find_all(words, 't', "elom")),
+            find_all_results(words.map(Ok), 't', "elom")
+        );
+    }
+
+    #[test]
+    fn find_all_results_returns_first_error() {
+        let mut checked = 0;
+        let words = [Ok("tote"), Err(1), Ok("motel"), Err(2)]
+            .into_iter()
+            .inspect(|_| checked += 1);
+
+        assert_eq!(Err(1), find_all_results(words, 't', "elom"));
+        assert_eq!(2, checked);
+    }
+
+    #[test]
+    fn for_each_answer_matches_find_all_order() {
+        let words = ["tome", "soapy", "motel", "tote"];
+        let mut answers = Vec::new();
+        for_each_answer(words, 't', "elom", |ans| answers.push(ans));
+
+        assert_eq!(find_all(words, 't', "elom"), answers);
+    }
+
+    #[test]
+    fn pangrams_only_disabled_by_default() {
+        assert!(!Scoring::default().pangrams_only);
+    }
+
+    #[test]
+    fn pangrams_only_returns_only_pangrams() {
+        let scoring = Scoring {
+            pangrams_only: true,
+            ..Scoring::default()
+        };
+
+        assert!(check_word_with("tote", 't', "elom", &scoring).is_none());
+        assert_eq!(
+            Some(12),
+            check_word_with("motel", 't', "elom", &scoring).map(|a| a.score)
+        );
+
+        let index = DictionaryIndex::new(["tote", "motel", "tome", "molten"]);
+        let puzzle = Puzzle::new('t', "elom").unwrap();
+        assert_eq!(
+            vec!["motel"],
+            words_of(&index.solve_with(&puzzle, &scoring))
+        );
+    }
+
+    #[test]
+    fn pangrams_only_matches_filtering() {
+        let scoring = Scoring {
+            pangrams_only: true,
+            ..Scoring::default()
+        };
+        let words = [
+            "tote",
+            "motel",
+            "tome",
+            "metol",
+            "mot\u{e9}l",
+            "motelt\u{e9}",
+        ];
+
+        for (required, extra) in [('t', "elom"), ('t', "elom\u{e9}")] {
+            let index = DictionaryIndex::new(words);
+            let puzzle = Puzzle::new(required, extra).unwrap();
+
+            let mut filtered = index.solve(&puzzle);
+            filtered.retain(|a| a.is_pangram);
+
+            assert_eq!(filtered, index.solve_with(&puzzle, &scoring));
+
+            let scalar: Vec<Answer> = words
+                .iter()
+                .filter_map(|w| check_word_with(w, required, extra, &scoring))
+                .collect();
+            assert_eq!(filtered, scalar);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "unicode")]
+    fn unicode_decomposed_word_matches_composed_puzzle() {
+        let composed = check_word("caf\u{e9}s", 'c', "afs\u{e9}").unwrap();
+        let decomposed = check_word("cafe\u{301}s", 'c', "afs\u{e9}").unwrap();
+
+        assert_eq!(composed.score, decomposed.score);
+        assert_eq!(composed.is_pangram, decomposed.is_pangram);
+        assert_eq!("cafe\u{301}s", decomposed.word);
+    }
+
+    #[test]
+    #[cfg(feature = "unicode")]
+    fn unicode_decomposed_puzzle_matches_composed_word() {
+        assert_eq!(
+            check_word("caf\u{e9}s", 'c', "afs\u{e9}"),
+            check_word("caf\u{e9}s", 'c', "afse\u{301}")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "unicode")]
+    fn unicode_find_all_same_results_for_both_forms() {
+        let composed = find_all(["caf\u{e9}", "fac\u{e9}s"], 'c', "afs\u{e9}");
+        let decomposed = find_all(["cafe\u{301}", "face\u{301}s"], 'c', "afs\u{e9}");
+
+        assert_eq!(2, composed.len());
+        assert_eq!(
+            composed
+                .iter()
+                .map(|a| (a.score, a.is_pangram))
+                .collect::<Vec<_>>(),
+            decomposed
+                .iter()
+                .map(|a| (a.score, a.is_pangram))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    /// Scoring that gives bonus points for the letters `z` and `q`.
+    fn rare_letter_scoring() -> Scoring {
+        Scoring {
+            letter_bonus: [('z', 5), ('q', 10)].into_iter().collect(),
+            ..Scoring::default()
+        }
+    }
+
+    #[test]
+    fn letter_bonus_empty_by_default() {
+        assert!(Scoring::default().letter_bonus.is_empty());
+    }
+
+    #[test]
+    fn letter_bonus_adds_to_score() {
+        let scoring = rare_letter_scoring();
+        let answer = check_word_with("zone", 'o', "zneqa", &scoring).unwrap();
 
-    for w in words {
-        match check_word(w.as_ref(), required, extra) {
-            Some(ans) => answers.push(ans),
-            None => {}
-        };
+        assert_eq!(1 + 5, answer.score);
     }
 
-    answers
-}
+    #[test]
+    fn letter_bonus_counts_each_letter_once() {
+        let scoring = rare_letter_scoring();
+        let answer = check_word_with("zozo", 'o', "zneqa", &scoring).unwrap();
 
-/// Test if the given word is a valid answer to the spelling bee, and return
-/// scoring information if it is an answer. If the word is not an answer than
-/// `None` will be returned.
-///
-/// # Notes
-/// A word is considered an answer if it is at least four letters long, at least
-/// one character matches `required`, and the remaining letters match of the
-/// characters in `extra` or the required character. A pangram is when a
-/// word's letters match both the required character, and every character listed
-/// in `extra`.
-///
-/// Scoring is determined with the following rules:
-///     1. Words of length four are worth one point.
-///     2. Words longer than four letters are worth their length in points.
-///     3. A pangram word gets an additional seven points.
-///
-/// # Examples
-/// ```
-/// use spellingbee::check_word;
-/// assert!(check_word("loon", 'o', "unrlap").is_some());
-/// assert!(check_word("unpopular", 'o', "unrlap").unwrap().is_pangram);
-/// assert_eq!(7, check_word("pronoun", 'o', "unrlap").unwrap().score);
-/// assert!(check_word("foobar", 'o', "unrlap").is_none());
-/// ```
-pub fn check_word(word: &str, required: char, extra: &str) -> Option<Answer> {
-    // Words must be at least four characters.
-    if word.len() < WORD_MIN_LENGTH {
-        return None;
+        assert_eq!(1 + 5, answer.score);
     }
 
-    // Words must also contain the required character.
-    if !word.contains(required) {
-        return None;
+    #[test]
+    fn letter_bonus_without_bonus_letters() {
+        let scoring = rare_letter_scoring();
+        assert_eq!(
+            1,
+            check_word_with("neon", 'o', "zneqa", &scoring)
+                .unwrap()
+                .score
+        );
     }
 
-    // Words can only contain characters matching required or extra.
-    if word
-        .chars()
-        .into_iter()
-        .all(|x| x == required || extra.chars().any(|e| e == x))
-    {
-        // Count the number of unique letters that were matched. We do this with
-        // a O(nm) algorithm to avoid allocating a hashmap since both n and m
-        // are small.
-        let mut uniq_count = 1; // The required char must always match.
+    #[test]
+    fn letter_bonus_masked_matches_scalar() {
+        let scoring = rare_letter_scoring();
+        let index = DictionaryIndex::new(["zone", "neon", "ozone", "azonqe"]);
+        let puzzle = Puzzle::new('o', "zneqa").unwrap();
 
-        for e in extra.chars() {
-            if word.chars().any(|w| w == e) {
-                uniq_count += 1;
-            }
-        }
+        let scalar: Vec<Answer> = ["zone", "neon", "ozone", "azonqe"]
+            .iter()
+            .filter_map(|w| check_word_with(w, 'o', "zneqa", &scoring))
+            .collect();
+
+        assert_eq!(scalar, index.solve_with(&puzzle, &scoring));
+        assert_eq!(6 + 7 + 15, scalar[3].score);
+    }
 
-        let is_pangram = uniq_count == 1 + extra.len();
+    #[test]
+    fn letter_bonus_breakdown() {
+        let scoring = rare_letter_scoring();
+        let answer = check_word_with("ozone", 'o', "zneqa", &scoring).unwrap();
+        let breakdown = score_breakdown(&answer, &scoring);
 
-        // Scoring uses the following rules:
-        //  1. Four letter words score 1 point.
-        //  2. Five letter or longer words score their length in points.
-        //  3. A pangram receives an extra 7 points.
-        let mut score: i32 = 1;
+        assert_eq!(5, breakdown.base);
+        assert_eq!(5, breakdown.letter_bonus);
+        assert_eq!(answer.score, breakdown.total);
+    }
 
-        if word.len() >= SCORE_MIN_LENGTH {
-            score = word.len() as i32;
-        }
+    #[test]
+    fn puzzle_answer_counts_for_each_center() {
+        let words: Vec<String> = ["unpopular", "pronoun", "loon", "pull", "roll", "lunar"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+
+        let expected: Vec<(String, usize)> = [
+            ("lanopru", 5),
+            ("nalopru", 4),
+            ("oalnpru", 4),
+            ("ralnopu", 4),
+            ("ualnopr", 4),
+            ("palnoru", 3),
+            ("alnopru", 2),
+        ]
+        .iter()
+        .map(|&(p, n)| (p.to_string(), n))
+        .collect();
+
+        assert_eq!(expected, puzzle_answer_counts(&words));
+    }
+
+    #[test]
+    fn puzzle_answer_counts_without_pangram_words() {
+        let words: Vec<String> = ["tote", "motel", "can't"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        assert!(puzzle_answer_counts(&words).is_empty());
+    }
+
+    #[test]
+    fn puzzle_answer_counts_merges_repeated_letter_sets() {
+        let words: Vec<String> = ["unpopular", "popularun", "loon"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let counts = puzzle_answer_counts(&words);
+
+        assert_eq!(7, counts.len());
+        assert_eq!(("lanopru".to_string(), 3), counts[0]);
+    }
+
+    #[test]
+    fn empty_extra_accepts_only_required_letter() {
+        let answer = check_word("tttt", 't', "").unwrap();
+
+        assert!(answer.is_pangram);
+        assert_eq!(1 + PANGRAM_BONUS, answer.score);
+        assert_eq!(None, check_word("tote", 't', ""));
+    }
+
+    #[test]
+    fn empty_extra_masked_matches_scalar() {
+        let index = DictionaryIndex::new(["tttt", "tote", "ttttt"]);
+        let answers = index.find_all('t', "");
+
+        assert_eq!(find_all(["tttt", "tote", "ttttt"], 't', ""), answers);
+        assert_eq!(vec!["tttt", "ttttt"], words_of(&answers));
+        assert!(answers.iter().all(|a| a.is_pangram));
+    }
+
+    #[test]
+    fn top_answers_matches_sorted_and_truncated() {
+        let words = [
+            "tote", "motel", "tome", "molten", "meteor", "totem", "mottle",
+        ];
 
-        if is_pangram {
-            score += PANGRAM_SCORE_BOOST;
+        for n in [1, 2, 4] {
+            let mut expected = find_all_sorted(words, 't', "elom");
+            expected.truncate(n);
+
+            assert_eq!(expected, top_answers(words, 't', "elom", n));
         }
+    }
 
-        // Return answer as the word, its score and if it was a pangram.
-        Some(Answer {
-            word: word.to_string(),
-            score,
-            is_pangram,
-        })
-    } else {
-        None
+    #[test]
+    fn top_answers_more_than_answer_count() {
+        let words = ["tote", "motel", "tome", "soapy"];
+        assert_eq!(
+            find_all_sorted(words, 't', "elom"),
+            top_answers(words, 't', "elom", 10)
+        );
     }
-}
 
-#[cfg(test)]
-#[allow(dead_code)]
-mod tests {
-    use crate::{check_word, find_all};
+    #[test]
+    fn top_answers_zero_is_empty() {
+        assert!(top_answers(["motel"], 't', "elom", 0).is_empty());
+    }
 
     #[test]
-    fn empty_word_is_not_valid() {
-        assert_eq!(None, check_word("", 'c', "ab"));
+    fn top_answers_ties_use_canonical_order() {
+        let words = ["tote", "tome", "mote", "toot"];
+        assert_eq!(
+            vec!["mote", "tome"],
+            words_of(&top_answers(words, 't', "elom", 2))
+        );
     }
 
     #[test]
-    fn word_less_than_four_chars_not_valid() {
-        assert_eq!(None, check_word("c", 'c', "ab"));
-        assert_eq!(None, check_word("ca", 'c', "ab"));
-        assert_eq!(None, check_word("cab", 'c', "ab"));
+    fn distinct_puzzle_letters_three_letters() {
+        assert_eq!(3, distinct_puzzle_letters("tote", 't', "elom"));
+    }
 
-        assert_eq!(None, check_word("d", 'c', "ab"));
-        assert_eq!(None, check_word("do", 'c', "ab"));
-        assert_eq!(None, check_word("dog", 'c', "ab"));
+    #[test]
+    fn distinct_puzzle_letters_pangram() {
+        assert_eq!(5, distinct_puzzle_letters("motel", 't', "elom"));
+        assert_eq!(7, distinct_puzzle_letters("unpopular", 'o', "unrlap"));
     }
 
     #[test]
-    fn word_uses_required_valid() {
-        assert!(check_word("tttt", 't', "oe").is_some());
-        assert!(check_word("tttttt", 't', "oe").is_some());
-        assert!(check_word("ssss", 's', "oe").is_some());
+    fn distinct_puzzle_letters_without_required_letter() {
+        assert_eq!(4, distinct_puzzle_letters("mole", 't', "elom"));
+        assert_eq!(3, distinct_puzzle_letters("move", 't', "elom"));
     }
 
     #[test]
-    fn word_missing_required_not_valid() {
-        assert_eq!(None, check_word("oeoe", 't', "oe"));
-        assert_eq!(None, check_word("oooo", 't', "oe"));
+    fn merge_answers_disjoint_sets() {
+        let a = find_all(["tote", "tome"], 't', "elom");
+        let b = find_all(["motel"], 't', "elom");
+
+        assert_eq!(
+            vec!["tote", "tome", "motel"],
+            words_of(&merge_answers(a, b))
+        );
     }
 
     #[test]
-    fn word_can_use_extra_chars() {
+    fn merge_answers_higher_score_wins() {
+        let a = vec![Answer {
+            word: "tote".to_string(),
+            score: 1,
+            is_pangram: false,
+        }];
+        let b = vec![Answer {
+            word: "tote".to_string(),
+            score: 6,
+            is_pangram: false,
+        }];
+
+        let merged = merge_answers(a.clone(), b.clone());
+        assert_eq!(b, merged);
+        assert_eq!(b, merge_answers(b.clone(), a));
+    }
+
+    #[test]
+    fn merge_answers_keeps_pangram_flags() {
+        let a = find_all(["motel", "tote"], 't', "elom");
+        let b = find_all(["tome", "motel"], 't', "elom");
+        let merged = merge_answers(a, b);
+
+        assert_eq!(vec!["motel", "tote", "tome"], words_of(&merged));
+        assert!(merged[0].is_pangram);
+        assert!(!merged[1].is_pangram);
+    }
+
+    #[test]
+    fn merge_answers_tie_keeps_first() {
+        let first = Answer {
+            word: "tote".to_string(),
+            score: 1,
+            is_pangram: false,
+        };
+        let second = Answer {
+            is_pangram: true,
+            ..first.clone()
+        };
+
+        assert_eq!(
+            vec![first.clone()],
+            merge_answers(vec![first], vec![second])
+        );
+    }
+
+    #[test]
+    fn allowed_set_ascii_membership() {
+        let allowed = AllowedSet::new("elom");
+
+        assert!(allowed.is_ascii());
+        assert!("elom".chars().all(|c| allowed.contains(c)));
+        assert!(!allowed.contains('t'));
+        assert!(!allowed.contains('E'));
+        assert!(!allowed.contains('\u{e9}'));
+    }
+
+    #[test]
+    fn allowed_set_non_ascii_scans_letters() {
+        let allowed = AllowedSet::new("\u{e9}lom");
+
+        assert!(!allowed.is_ascii());
+        assert!(allowed.contains('\u{e9}'));
+        assert!(allowed.contains('l'));
+        assert!(!allowed.contains('e'));
+    }
+
+    #[test]
+    fn allowed_set_empty() {
+        let allowed = AllowedSet::new("");
+
+        assert!(allowed.is_ascii());
+        assert!(!allowed.contains('a'));
+        assert!(!allowed.contains('\0'));
+    }
+
+    #[test]
+    fn evaluate_guess_valid() {
+        assert_eq!(
+            GuessResult::Valid(check_word("motel", 't', "elom").unwrap()),
+            evaluate_guess("motel", 't', "elom")
+        );
+    }
+
+    #[test]
+    fn evaluate_guess_too_short() {
+        assert_eq!(GuessResult::TooShort, evaluate_guess("tot", 't', "elom"));
+        // Length is checked before the letters.
+        assert_eq!(GuessResult::TooShort, evaluate_guess("zoo", 't', "elom"));
+    }
+
+    #[test]
+    fn evaluate_guess_missing_required() {
+        assert_eq!(
+            GuessResult::MissingRequired,
+            evaluate_guess("mole", 't', "elom")
+        );
+    }
+
+    #[test]
+    fn evaluate_guess_illegal_letter() {
+        assert_eq!(
+            GuessResult::IllegalLetter('v'),
+            evaluate_guess("vote", 't', "elom")
+        );
+        // The first illegal letter is reported, even without the required
+        // letter.
+        assert_eq!(
+            GuessResult::IllegalLetter('s'),
+            evaluate_guess("soapy", 't', "elom")
+        );
+        assert_eq!(
+            GuessResult::IllegalLetter('T'),
+            evaluate_guess("Tote", 't', "elom")
+        );
+    }
+
+    #[test]
+    fn sanitize_letters_removes_zero_width_characters() {
+        assert_eq!(
+            "telomic",
+            sanitize_letters("t\u{200d}elo\u{200b}mic\u{feff}")
+        );
+    }
+
+    #[test]
+    fn sanitize_letters_removes_unicode_spaces() {
+        assert_eq!(
+            "telomic",
+            sanitize_letters("t\u{a0}e l\u{2009}o\tm\ni\u{3000}c")
+        );
+    }
+
+    #[test]
+    fn sanitize_letters_removes_punctuation() {
+        assert_eq!("telomic", sanitize_letters("[t] e-l-o-m-i-c."));
+        assert_eq!("t\u{e9}lomic", sanitize_letters("\"t\u{e9}lomic\""));
+        assert_eq!("", sanitize_letters("!?"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn diff_answers_fully_overlapping() {
+        let answers = find_all(["tote", "motel", "tome"], 't', "elom");
+        let previous = word_set(&["tome", "tote", "motel"]);
+
+        assert!(diff_answers(&answers, &previous).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn diff_answers_partially_overlapping() {
+        let answers = find_all(["tote", "motel", "tome"], 't', "elom");
+        let previous = word_set(&["motel", "vote"]);
+
+        assert_eq!(
+            vec![&answers[0], &answers[2]],
+            diff_answers(&answers, &previous)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn diff_answers_disjoint() {
+        let answers = find_all(["tote", "motel", "tome"], 't', "elom");
+        let previous = word_set(&["Tote", "soapy"]);
+
+        assert_eq!(
+            answers.iter().collect::<Vec<_>>(),
+            diff_answers(&answers, &previous)
+        );
+    }
+
+    #[test]
+    fn letters_used_by_partial_word() {
+        assert_eq!(vec!['o', 't'], letters_used("toot", 't', "elom"));
+        assert_eq!(vec!['e', 'm', 'o', 't'], letters_used("mote", 't', "elom"));
+    }
+
+    #[test]
+    fn letters_used_by_pangram() {
+        assert_eq!(
+            vec!['e', 'l', 'm', 'o', 't'],
+            letters_used("motel", 't', "elom")
+        );
+        assert_eq!(
+            vec!['a', 'l', 'n', 'o', 'p', 'r', 'u'],
+            letters_used("unpopular", 'o', "unrlap")
+        );
+    }
+
+    #[test]
+    fn letters_used_ignores_other_letters() {
+        assert_eq!(vec!['e', 'o', 't'], letters_used("vote", 't', "elom"));
+        assert_eq!(vec!['m', 'o'], letters_used("Tom", 't', "elom"));
+        assert!(letters_used("", 't', "elom").is_empty());
+    }
+
+    #[test]
+    fn public_constants_match_default_scoring() {
+        let scoring = Scoring::default();
+
+        assert_eq!(MIN_WORD_LENGTH, scoring.min_word_len);
+        assert_eq!(LONG_WORD_MIN_LENGTH, scoring.long_word_min_len);
+        assert_eq!(
+            PangramBonus::Flat(PANGRAM_BONUS),
+            scoring.pangram_bonus_mode
+        );
+    }
+
+    #[test]
+    fn public_constants_match_check_word() {
         assert!(check_word("tote", 't', "elom").is_some());
-        assert!(check_word("totet", 't', "elom").is_some());
-        assert!(check_word("mote", 't', "elom").is_some());
-        assert!(check_word("molet", 't', "elom").is_some());
+        assert_eq!(None, check_word("tot", 't', "elom"));
+        assert_eq!(MIN_WORD_LENGTH, "tote".len());
+
+        assert_eq!(1, check_word("tote", 't', "elom").unwrap().score);
+        assert_eq!(5, check_word("lotto", 't', "elom").unwrap().score);
+        assert_eq!(LONG_WORD_MIN_LENGTH, "lotto".len());
+
+        assert_eq!(
+            5 + PANGRAM_BONUS,
+            check_word("motel", 't', "elom").unwrap().score
+        );
+        assert_eq!(
+            Err(PuzzleError::WrongLetterCount(6)),
+            parse_puzzle("telomi")
+        );
+        assert!(parse_puzzle(&"telomic"[..PUZZLE_LETTER_COUNT]).is_ok());
     }
 
     #[test]
-    fn word_with_chars_not_in_required_or_extra_not_valid() {
-        assert_eq!(None, check_word("dote", 't', "elom"));
-        assert_eq!(None, check_word("note", 't', "elom"));
+    #[cfg(feature = "std")]
+    fn find_all_with_dict_strips_leading_bom() {
+        let dict = "\u{feff}tote\nmotel\n".as_bytes();
+        let answers = find_all_with_dict(dict, 't', "elom", false).unwrap();
+
+        assert_eq!(vec!["tote", "motel"], words_of(&answers));
     }
 
     #[test]
-    fn test_multiple_words() {
-        let words = vec![
-            "tote".to_string(),
-            "vote".to_string(),
-            "mote".to_string(),
-            "soapy".to_string(),
-        ];
-        let answers = find_all(words.iter(), 't', "elom");
-        assert_eq!(2, answers.len());
-        assert_eq!("tote", answers[0].word);
-        assert_eq!("mote", answers[1].word);
+    #[cfg(feature = "std")]
+    fn read_dict_only_strips_bom_at_start() {
+        let dict = "\u{feff}tote\n\u{feff}motel\n".as_bytes();
+        assert_eq!(vec!["tote"], read_dict(dict).unwrap());
     }
 
     #[test]
-    fn answer_contains_original_word() {
-        assert_eq!("motel", check_word("motel", 't', "elom").unwrap().word);
-        assert_eq!("tome", check_word("tome", 't', "elom").unwrap().word);
+    #[cfg(feature = "std")]
+    fn dictionary_stats_strips_leading_bom() {
+        let stats = dictionary_stats("\u{feff}tote\nmotel\n".as_bytes()).unwrap();
+
+        assert_eq!(0, stats.non_letter_words);
+        assert_eq!(Some("tote".to_string()), stats.shortest);
     }
 
     #[test]
-    fn pangram_uses_all_letters() {
-        assert_eq!(true, check_word("motel", 't', "elom").unwrap().is_pangram);
-        assert_eq!(true, check_word("emotel", 't', "elom").unwrap().is_pangram);
-        assert_eq!(false, check_word("motee", 't', "elom").unwrap().is_pangram);
-        assert_eq!(false, check_word("mote", 't', "elom").unwrap().is_pangram);
+    fn pangram_candidates_have_seven_distinct_letters() {
+        let words: Vec<String> = [
+            "motel",
+            "unpopular",
+            "emoticon",
+            "coastline",
+            "can't-do",
+            "telomic",
+        ]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+
+        assert_eq!(
+            vec!["unpopular", "emoticon", "telomic"],
+            find_pangram_candidates(&words)
+        );
     }
 
     #[test]
-    fn four_length_score_is_one() {
-        assert_eq!(1, check_word("tome", 't', "elom").unwrap().score);
-        assert_eq!(1, check_word("tell", 't', "elom").unwrap().score);
+    fn pangram_candidates_ignore_case() {
+        let words: Vec<String> = ["Unpopular", "Telomics", "MOTEL"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+
+        assert_eq!(vec!["Unpopular"], find_pangram_candidates(&words));
     }
 
     #[test]
-    fn score_equal_length_when_larger_than_four() {
-        assert_eq!(5, check_word("motee", 't', "elom").unwrap().score);
-        assert_eq!(5, check_word("tello", 't', "elom").unwrap().score);
-        assert_eq!(6, check_word("tomtom", 't', "elom").unwrap().score);
-        assert_eq!(9, check_word("tomtomtom", 't', "elom").unwrap().score);
+    fn pangram_candidates_empty_when_none_match() {
+        let words: Vec<String> = ["tote", "motel"].iter().map(|w| w.to_string()).collect();
+
+        assert!(find_pangram_candidates(&words).is_empty());
+        assert!(find_pangram_candidates(&[]).is_empty());
+    }
+
+    fn capped_scoring() -> Scoring {
+        Scoring {
+            max_word_score: Some(10),
+            ..Scoring::default()
+        }
     }
 
     #[test]
-    fn pangram_adds_seven_to_score() {
-        assert_eq!(12, check_word("motel", 't', "elom").unwrap().score);
-        assert_eq!(13, check_word("emotel", 't', "elom").unwrap().score);
+    fn max_word_score_disabled_by_default() {
+        assert_eq!(None, Scoring::default().max_word_score);
+        assert_eq!(16, check_word("unpopular", 'o', "unrlap").unwrap().score);
+    }
+
+    #[test]
+    fn max_word_score_clamps_long_pangram() {
+        let answer = check_word_with("unpopular", 'o', "unrlap", &capped_scoring()).unwrap();
+
+        assert!(answer.is_pangram);
+        assert_eq!(10, answer.score);
+    }
+
+    #[test]
+    fn max_word_score_leaves_short_words() {
+        let scoring = capped_scoring();
+
+        assert_eq!(
+            1,
+            check_word_with("loon", 'o', "unrlap", &scoring)
+                .unwrap()
+                .score
+        );
+        assert_eq!(
+            7,
+            check_word_with("pronoun", 'o', "unrlap", &scoring)
+                .unwrap()
+                .score
+        );
+    }
+
+    #[test]
+    fn max_word_score_masked_matches_scalar() {
+        let scoring = capped_scoring();
+        let puzzle = Puzzle::new('t', "elom").unwrap();
+
+        for word in ["motel", "tomtom", "tote", "emotel"] {
+            assert_eq!(
+                check_word_with(word, 't', "elom", &scoring),
+                puzzle.check_word_with(word, &scoring)
+            );
+        }
+    }
+
+    #[test]
+    fn max_word_score_breakdown() {
+        let answer = check_word("unpopular", 'o', "unrlap").unwrap();
+        let breakdown = score_breakdown(&answer, &capped_scoring());
+
+        assert_eq!(9, breakdown.base);
+        assert_eq!(7, breakdown.pangram_bonus);
+        assert_eq!(10, breakdown.total);
+    }
+
+    #[cfg(feature = "std")]
+    fn graded(submitted: &[&str]) -> Vec<SubmissionStatus> {
+        let submitted: Vec<String> = submitted.iter().map(|w| w.to_string()).collect();
+        let dictionary = word_set(&["tote", "motel", "tome", "vote", "tot"]);
+
+        grade_submission(&submitted, &dictionary, 't', "elom")
+            .into_iter()
+            .map(|(_, status)| status)
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn grade_submission_accepts_dictionary_answers() {
+        assert_eq!(
+            vec![SubmissionStatus::Accepted, SubmissionStatus::Accepted],
+            graded(&["motel", " tome "])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn grade_submission_rejects_puzzle_rule_breakers() {
+        // Too short, a letter outside the puzzle and no required letter, even
+        // when the word is in the dictionary.
+        assert_eq!(
+            vec![SubmissionStatus::NotAWord; 3],
+            graded(&["tot", "vote", "mole"])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn grade_submission_not_in_dictionary() {
+        assert_eq!(
+            vec![
+                SubmissionStatus::NotInDictionary,
+                SubmissionStatus::NotInDictionary
+            ],
+            graded(&["toot", "mote"])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn grade_submission_keeps_submitted_words_in_order() {
+        let submitted = vec!["toot".to_string(), "motel".to_string()];
+        let graded = grade_submission(&submitted, &word_set(&["motel"]), 't', "elom");
+
+        assert_eq!(
+            vec![
+                ("toot".to_string(), SubmissionStatus::NotInDictionary),
+                ("motel".to_string(), SubmissionStatus::Accepted),
+            ],
+            graded
+        );
     }
 }
@@ -13,11 +13,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 ////////////////////////////////////////////////////////////////////////////////
-// TODO: Windows support since it doesn't have a builtin dictionary?
-use clap::Parser;
-use spellingbee::{find_all, Answer};
-use std::io::{BufRead, BufReader};
+mod progress;
+
+use clap::{CommandFactory, ErrorKind, Parser, Subcommand, ValueEnum};
+use spellingbee::{
+    apply_bonuses, check_word, check_word_ci, count_char, dictionary_stats, diff_answers,
+    difficulty, distinct_puzzle_letters, evaluate_guess, filter_answers, filter_length,
+    filter_prefix, filter_prefix_ci, find_all, find_all_ci, find_all_with_dict,
+    find_pangram_candidates, genius_threshold, group_anagrams, group_by_length, is_candidate_word,
+    merge_answers, near_misses, parse_puzzle, read_dict, remove_words, sanitize_letters,
+    score_breakdown, sort_answers, sort_answers_by, summarize, two_letter_counts, validate_puzzle,
+    words_to_reach, Answer, DictionaryIndex, Error, GuessResult, PuzzleError, Scoring, SortKey,
+    SortOptions, TieBreak, MIN_WORD_LENGTH,
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, BufWriter, IsTerminal, Read, Write};
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 use std::{fs::File, path::Path};
 
 const APP_SHORT_NAME: &str = "spellingbee";
@@ -27,62 +40,2178 @@ const APP_SHORT_NAME: &str = "spellingbee";
 #[clap(name = "Spellingbee")]
 #[clap(author = "Scott MacDonald <scott@smacdo.com>")]
 #[clap(about = "Finds answers to the NYT spelling bee game.")]
+#[clap(subcommand_negates_reqs = true)]
 struct CliParams {
-    /// Path to a dictionary file (one word per line).
+    #[clap(subcommand)]
+    command: Option<Command>,
+    /// Path to a dictionary file (one word per line), or `-` to read the
+    /// dictionary from standard input. Can be given more than once to search
+    /// several dictionaries. Defaults to the system dictionary, which is
+    /// /usr/share/dict/words on most platforms. Windows does not have a system
+    /// dictionary so the SPELLINGBEE_DICT environment variable is used instead.
     #[clap(short = 'd')]
-    #[clap(default_value = "/usr/share/dict/words")]
-    dict_path: PathBuf,
+    dict_paths: Vec<PathBuf>,
+    /// Path to a TOML file with default values for the dictionary path
+    /// (`dict_path`) and the puzzle letters (`required` and `extra`). Options
+    /// given on the command line take priority over the config file.
+    #[clap(long)]
+    config: Option<PathBuf>,
+    /// Show how much of each dictionary has been read. Only shown when
+    /// standard error is a terminal, and needs the `progress` feature.
+    #[clap(long)]
+    progress: bool,
+    /// Read the words from this column (starting at 1) of comma separated
+    /// dictionaries. Quoted fields are not supported.
+    #[clap(
+        long = "csv-column",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    csv_column: Option<usize>,
+    /// Decompress the dictionaries with gzip. Dictionaries ending in `.gz` are
+    /// always decompressed.
+    #[clap(long)]
+    gzip: bool,
+    /// Format used when printing the answers.
+    #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// Order that answers are printed in.
+    #[clap(long, value_enum, default_value_t = SortMode::Score)]
+    sort: SortMode,
+    /// How answers that are tied under `--sort` are ordered. Without it ties
+    /// use the default order of highest score, pangrams and then alphabetical.
+    #[clap(long = "tie-break", value_enum)]
+    tie_break: Option<TieBreakMode>,
+    /// Only show answers worth at least this many points.
+    #[clap(long = "min-score")]
+    min_score: Option<i32>,
+    /// Extra points for answers that use a letter, given as `letter=points`.
+    /// Can be given more than once, and combined with `--min-score` to only
+    /// show answers using rare letters.
+    #[clap(long = "letter-bonus", value_parser = parse_letter_bonus)]
+    letter_bonus: Vec<(char, i32)>,
+    /// Only show this many of the highest scoring answers.
+    #[clap(long)]
+    top: Option<usize>,
+    /// Group the answers by their length.
+    #[clap(long = "by-length")]
+    by_length: bool,
+    /// Print how many answers start with each two letter prefix rather than
+    /// printing the answers.
+    #[clap(long, conflicts_with = "by-length")]
+    hints: bool,
+    /// Path to a file of words (one per line) to leave out of the answers,
+    /// such as words that were already found.
+    #[clap(long)]
+    exclude: Option<PathBuf>,
+    /// Path to a file of answers from a previous run (one per line). Only the
+    /// answers that are not in it are shown, along with how many are new.
+    #[clap(long)]
+    diff: Option<PathBuf>,
+    /// Path to a file of `word=points` lines giving bonus points that are
+    /// added to the score of matching answers.
+    #[clap(long = "bonus-file")]
+    bonus_file: Option<PathBuf>,
+    /// Show how each answer's score was calculated.
+    #[clap(long, conflicts_with_all = &["hints", "by-length"])]
+    explain: bool,
+    /// Print groups of answers that are anagrams of each other.
+    #[clap(long, conflicts_with_all = &["hints", "by-length", "explain"])]
+    anagrams: bool,
+    /// Show how many different puzzle letters each answer uses.
+    #[clap(long = "letters-used")]
+    letters_used: bool,
+    /// Text printed in front of pangrams.
+    #[clap(long, default_value = "*")]
+    marker: String,
+    /// When to highlight pangrams with color. `auto` only uses color when
+    /// printing to a terminal.
+    #[clap(long, value_enum, default_value_t = ColorMode::Auto)]
+    color: ColorMode,
+    /// Only show answers that start with these letters.
+    #[clap(long = "starts-with")]
+    starts_with: Option<String>,
+    /// Only show answers that are exactly this many letters long.
+    #[clap(long = "exact-length")]
+    exact_length: Option<usize>,
+    /// Only show answers that use the required letter exactly this many times.
+    #[clap(long = "required-count")]
+    required_count: Option<usize>,
+    /// Your current score. Only the highest scoring answers needed to reach
+    /// the genius rank from this score are shown. Use with `--exclude` to
+    /// leave out the words that were already found.
+    #[clap(long = "current-score")]
+    current_score: Option<i32>,
+    /// Only show answers that are pangrams.
+    #[clap(long)]
+    pangrams: bool,
+    /// Only print the number of answers found.
+    #[clap(long)]
+    count: bool,
+    /// Only print how hard the puzzle is (easy, medium or hard), rated from all
+    /// of its answers.
+    #[clap(long, conflicts_with = "count")]
+    difficulty: bool,
+    /// Do not print the word count and total score after the answers.
+    #[clap(long = "no-summary")]
+    no_summary: bool,
+    /// Print what happened while solving to standard error. Use `-vv` to also
+    /// print why each dictionary word is not an answer.
+    #[clap(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Write the answers to this file instead of standard output.
+    #[clap(long)]
+    output: Option<PathBuf>,
+    /// Ignore letter case when matching dictionary words.
+    #[clap(short = 'i', long = "ignore-case")]
+    ignore_case: bool,
+    /// All seven puzzle letters with the required center letter first, as an
+    /// alternative to passing the required and extra characters separately.
+    /// Anything that is not a letter, such as spaces or punctuation, is ignored.
+    #[clap(long, conflicts_with_all = &["required-char", "extra-chars"])]
+    puzzle: Option<String>,
+    /// Read all seven puzzle letters from the clipboard, with the required
+    /// center letter first. Anything that is not a letter is ignored. Needs the
+    /// `clipboard` feature.
+    #[clap(
+        long,
+        conflicts_with_all = &["puzzle", "required-char", "extra-chars", "interactive"]
+    )]
+    clipboard: bool,
+    /// After the answers, list words that are one letter away from being an
+    /// answer because they use one letter not in the puzzle or leave out the
+    /// required letter.
+    #[clap(
+        long = "near-misses",
+        conflicts_with_all = &["interactive", "format", "count", "difficulty"]
+    )]
+    near_misses: bool,
+    /// Print each answer as soon as it is read from the dictionaries, in
+    /// dictionary order. The answers are not sorted or filtered and no summary
+    /// is printed, but the dictionaries do not need to fit in memory.
+    #[clap(
+        long,
+        conflicts_with_all = &[
+            "interactive", "format", "sort", "tie-break", "by-length", "hints", "explain",
+            "anagrams", "count", "difficulty", "top", "current-score", "diff", "letter-bonus",
+            "pangrams", "min-score", "exclude", "starts-with", "exact-length", "required-count",
+            "bonus-file", "letters-used", "color", "near-misses",
+        ]
+    )]
+    stream: bool,
+    /// Load the dictionaries once and then repeatedly read puzzles from
+    /// standard input, one per line, printing the answers to each.
+    #[clap(long, conflicts_with_all = &["puzzle", "required-char", "extra-chars"])]
+    interactive: bool,
+    /// Character required to be in every answer.
+    #[clap(required_unless_present_any = &["puzzle", "clipboard", "interactive", "config"])]
+    required_char: Option<char>,
+    /// Extra characters allowed to be in an answer.
+    #[clap(required_unless_present_any = &["puzzle", "clipboard", "interactive", "config"])]
+    extra_chars: Option<String>,
+}
+
+/// Subcommands that do something other than solve a puzzle.
+#[derive(Subcommand)]
+enum Command {
+    /// Check the dictionaries for problems and print statistics about them
+    /// without solving a puzzle.
+    Validate,
+    /// Make up a puzzle from a random dictionary word that has seven different
+    /// letters, and print its required and extra letters.
+    Generate {
+        /// Seed for choosing the puzzle, so the same puzzle is generated every
+        /// time. A different puzzle is chosen on each run without it.
+        #[clap(long)]
+        seed: Option<u64>,
+        /// Also print the answers to the generated puzzle.
+        #[clap(long)]
+        answers: bool,
+    },
+}
+
+/// Dictionary used on Linux and most other unix platforms when none is given on
+/// the command line or in a config file.
+const UNIX_DICT_PATH: &str = "/usr/share/dict/words";
+
+/// Dictionary used on macOS when `UNIX_DICT_PATH` is missing.
+const MACOS_FALLBACK_DICT_PATH: &str = "/usr/share/dict/web2";
+
+/// Dictionary path that reads the word list bundled with the `bundled-dict`
+/// feature instead of a file.
+#[cfg(feature = "bundled-dict")]
+const BUNDLED_DICT_PATH: &str = ":bundled";
+
+/// Environment variable naming the default dictionary on Windows, which does
+/// not come with a dictionary.
+const DICT_ENV_VAR: &str = "SPELLINGBEE_DICT";
+
+/// Platforms with different rules for finding the default dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Platform {
+    Unix,
+    MacOs,
+    Windows,
+}
+
+impl Platform {
+    /// The platform this program was built for.
+    fn current() -> Platform {
+        if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else if cfg!(target_os = "windows") {
+            Platform::Windows
+        } else {
+            Platform::Unix
+        }
+    }
+}
+
+/// Get the dictionary to use when none is given on the command line or in a
+/// config file, or `None` if this platform does not have one.
+fn default_dict_path() -> Option<PathBuf> {
+    select_dict_path(
+        Platform::current(),
+        |path| path.exists(),
+        std::env::var_os(DICT_ENV_VAR).map(PathBuf::from),
+    )
+}
+
+/// Get the bundled word list to use when there is no system dictionary.
+#[cfg(feature = "bundled-dict")]
+fn bundled_dict_path() -> Option<PathBuf> {
+    Some(PathBuf::from(BUNDLED_DICT_PATH))
+}
+
+/// There is no bundled word list without the `bundled-dict` feature.
+#[cfg(not(feature = "bundled-dict"))]
+fn bundled_dict_path() -> Option<PathBuf> {
+    None
+}
+
+/// Choose the default dictionary for `platform`. `exists` tests if a path is
+/// present, and `env_path` is the value of the `DICT_ENV_VAR` environment
+/// variable.
+fn select_dict_path(
+    platform: Platform,
+    exists: impl Fn(&Path) -> bool,
+    env_path: Option<PathBuf>,
+) -> Option<PathBuf> {
+    match platform {
+        Platform::Unix => Some(PathBuf::from(UNIX_DICT_PATH)),
+        Platform::MacOs => {
+            let fallback = Path::new(MACOS_FALLBACK_DICT_PATH);
+
+            if !exists(Path::new(UNIX_DICT_PATH)) && exists(fallback) {
+                Some(fallback.to_path_buf())
+            } else {
+                Some(PathBuf::from(UNIX_DICT_PATH))
+            }
+        }
+        Platform::Windows => env_path,
+    }
+}
+
+/// Default values for command line options loaded from a config file.
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(deny_unknown_fields))]
+struct Config {
+    /// Path to the dictionary file.
+    dict_path: Option<PathBuf>,
     /// Character required to be in every answer.
-    required_char: char,
+    required: Option<char>,
     /// Extra characters allowed to be in an answer.
-    extra_chars: String,
+    extra: Option<String>,
+}
+
+impl Config {
+    /// Load the config file at `path`.
+    #[cfg(feature = "config")]
+    fn load(path: &Path) -> Result<Config, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        toml::from_str(&text).map_err(|err| err.to_string())
+    }
+
+    /// Config files can only be loaded when the `config` feature is enabled.
+    #[cfg(not(feature = "config"))]
+    fn load(_path: &Path) -> Result<Config, String> {
+        Err("config file support was not enabled when spellingbee was built".to_string())
+    }
+
+    /// Fill in any options in `args` that were not given on the command line
+    /// with the values from this config. Puzzle letters are only used when no
+    /// puzzle letters were given on the command line.
+    fn apply(self, args: &mut CliParams) {
+        if args.dict_paths.is_empty() {
+            args.dict_paths.extend(self.dict_path);
+        }
+
+        if args.puzzle.is_none() && !args.interactive {
+            if args.required_char.is_none() {
+                args.required_char = self.required;
+            }
+
+            if args.extra_chars.is_none() {
+                args.extra_chars = self.extra;
+            }
+        }
+    }
+}
+
+/// Formats that answers can be printed in.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// One answer per line with its score, pangrams marked with `*`.
+    Text,
+    /// A JSON array of answer objects.
+    #[cfg(feature = "serde")]
+    Json,
+    /// Tab separated `word`, `score` and `is_pangram` columns with a header
+    /// row.
+    Tsv,
+}
+
+/// When pangrams are highlighted with color.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorMode {
+    /// Use color when standard output is a terminal.
+    Auto,
+    /// Always use color, even when output is piped to a file.
+    Always,
+    /// Never use color.
+    Never,
+}
+
+impl ColorMode {
+    /// Decide if color should be used when printing the answers. `to_stdout`
+    /// is false when the answers are written to a file instead of standard
+    /// output.
+    fn enabled(self, to_stdout: bool) -> bool {
+        match self {
+            ColorMode::Auto => to_stdout && io::stdout().is_terminal(),
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
+    }
+}
+
+/// Orders that answers can be printed in.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SortMode {
+    /// Highest scoring answers first, with pangrams before all other answers.
+    Score,
+    /// Alphabetical order.
+    Alpha,
+    /// Shortest answers first.
+    Length,
+}
+
+/// Orders that tied answers can be printed in.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TieBreakMode {
+    /// Alphabetical order.
+    Alpha,
+    /// Longest answers first.
+    Length,
+    /// Pangrams before other answers.
+    Pangram,
+}
+
+impl From<TieBreakMode> for TieBreak {
+    fn from(mode: TieBreakMode) -> Self {
+        match mode {
+            TieBreakMode::Alpha => TieBreak::Alpha,
+            TieBreakMode::Length => TieBreak::Length,
+            TieBreakMode::Pangram => TieBreak::Pangram,
+        }
+    }
+}
+
+impl From<SortMode> for SortKey {
+    fn from(mode: SortMode) -> Self {
+        match mode {
+            SortMode::Score => SortKey::Score,
+            SortMode::Alpha => SortKey::Alpha,
+            SortMode::Length => SortKey::Length,
+        }
+    }
 }
 
 /// Application entry point.
 fn main() {
-    let args = CliParams::parse();
+    let mut args = CliParams::parse();
 
-    // Print the matching words or print any errors encountered when trying to
-    // load the dictionary.
-    let answers = find_all_with_dict(args.dict_path, args.required_char, &args.extra_chars);
+    if let Some(path) = args.config.clone() {
+        match Config::load(&path) {
+            Ok(config) => config.apply(&mut args),
+            Err(err) => {
+                eprintln!(
+                    "{} error: Failed to load config file {} ({})",
+                    APP_SHORT_NAME,
+                    path.display(),
+                    err
+                );
+                std::process::exit(1);
+            }
+        }
+    }
 
-    match answers {
-        Ok(mut answers) => {
-            // Print pangrams answers before all other answers, but make sure
-            // always show answers in order of descending score.
-            answers.sort_unstable_by_key(|a| -a.score);
+    if args.progress {
+        if let Err(err) = progress::supported() {
+            eprintln!("{} error: Cannot show progress ({})", APP_SHORT_NAME, err);
+            std::process::exit(1);
+        }
+    }
+
+    if args.dict_paths.is_empty() {
+        // Fall back to the bundled word list when the system dictionary is
+        // missing, if there is one.
+        let default_path = default_dict_path()
+            .filter(|path| bundled_dict_path().is_none() || path.exists())
+            .or_else(bundled_dict_path);
 
-            for ans in answers.iter().filter(|&a| a.is_pangram) {
-                println!("* {:<2} {}", ans.score, ans.word);
+        match default_path {
+            Some(path) => args.dict_paths.push(path),
+            None => {
+                eprintln!(
+                    "{} error: No default dictionary on this platform, pass one with -d or set {}",
+                    APP_SHORT_NAME, DICT_ENV_VAR
+                );
+                std::process::exit(1);
             }
+        }
+    }
+
+    let mut out = open_output(args.output.as_deref());
 
-            for ans in answers.iter().filter(|&a| !a.is_pangram) {
-                println!("  {:<2} {}", ans.score, ans.word);
+    if let Err(err) = run(&args, &mut out).and_then(|()| out.flush()) {
+        eprintln!(
+            "{} error: Failed to write the answers ({})",
+            APP_SHORT_NAME, err
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Open the file at `path` that answers are written to, or standard output
+/// when there is no path. If the file cannot be created an error is printed
+/// and the program exits.
+fn open_output(path: Option<&Path>) -> Box<dyn Write> {
+    match path {
+        Some(path) => match File::create(path) {
+            Ok(file) => Box::new(BufWriter::new(file)),
+            Err(err) => {
+                eprintln!(
+                    "{} error: Failed to create output file {} ({})",
+                    APP_SHORT_NAME,
+                    path.display(),
+                    err
+                );
+                std::process::exit(1);
             }
+        },
+        None => Box::new(io::stdout()),
+    }
+}
+
+/// Do whatever the command line `args` asked for, writing the results to
+/// `out`.
+fn run(args: &CliParams, out: &mut dyn Write) -> io::Result<()> {
+    match args.command {
+        Some(Command::Validate) => {
+            return validate_dicts(out, &args.dict_paths, DictOptions::from_args(args));
+        }
+        Some(Command::Generate { seed, answers }) => {
+            return generate_puzzle(out, args, seed, answers);
+        }
+        None => {}
+    }
+
+    if args.interactive {
+        return run_interactive(out, args);
+    }
+
+    let (required, extra) = puzzle_letters(args);
+
+    if args.stream {
+        return stream_answers(out, args, required, &extra);
+    }
+
+    // Near misses and verbose output both need every dictionary word rather
+    // than only the answers.
+    if args.near_misses || args.verbose > 0 {
+        let words = load_dicts(&args.dict_paths, DictOptions::from_args(args));
+        log(args, 1, format_args!("loaded {} words", words.len()));
+
+        let answers = if args.ignore_case {
+            find_all_ci(&words, required, &extra)
+        } else {
+            find_all(&words, required, &extra)
+        };
+
+        log(args, 1, format_args!("found {} answers", answers.len()));
+
+        if args.verbose >= 2 {
+            log_rejected_words(args, &words, &answers, required, &extra);
+        }
+
+        print_answers(out, args, required, &extra, answers)?;
+
+        if args.near_misses {
+            print_near_misses(out, &words, required, &extra, args.ignore_case)?;
+        }
+
+        return Ok(());
+    }
+
+    let answers = find_all_in_dicts(
+        &args.dict_paths,
+        required,
+        &extra,
+        args.ignore_case,
+        DictOptions::from_args(args),
+    );
+
+    print_answers(out, args, required, &extra, answers)
+}
+
+/// Get the rules for scoring answers from the command line options.
+fn scoring(args: &CliParams) -> Scoring {
+    Scoring {
+        letter_bonus: args.letter_bonus.iter().copied().collect(),
+        ..Scoring::default()
+    }
+}
+
+/// Parse a `--letter-bonus` value such as `z=5` into the letter and its extra
+/// points.
+fn parse_letter_bonus(value: &str) -> Result<(char, i32), String> {
+    let (letter, points) = value
+        .split_once('=')
+        .ok_or_else(|| "expected a letter and points like `z=5`".to_string())?;
+
+    let mut chars = letter.trim().chars();
+    let letter = match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_alphabetic() => c,
+        _ => return Err(format!("'{}' is not a single letter", letter.trim())),
+    };
+
+    let points = points
+        .trim()
+        .parse::<i32>()
+        .map_err(|err| format!("invalid points '{}' ({})", points.trim(), err))?;
+
+    Ok((letter, points))
+}
+
+/// Print `message` to standard error when the verbosity from `-v` is at least
+/// `level`.
+fn log(args: &CliParams, level: u8, message: fmt::Arguments) {
+    if args.verbose >= level {
+        eprintln!("{} info: {}", APP_SHORT_NAME, message);
+    }
+}
+
+/// Print the reason each word in `words` that is not one of the `answers` was
+/// rejected.
+fn log_rejected_words(
+    args: &CliParams,
+    words: &[String],
+    answers: &[Answer],
+    required: char,
+    extra: &str,
+) {
+    let answer_words: HashSet<&str> = answers.iter().map(|a| a.word.as_str()).collect();
+
+    for word in words.iter().filter(|w| !answer_words.contains(w.as_str())) {
+        let reason = rejection_reason(word, required, extra, args.ignore_case);
+        log(args, 2, format_args!("rejected {}: {}", word, reason));
+    }
+}
+
+/// Explain why `word` is not an answer to the puzzle. Letter case is ignored
+/// when `ignore_case` is true.
+fn rejection_reason(word: &str, required: char, extra: &str, ignore_case: bool) -> String {
+    let (word, required, extra) = if ignore_case {
+        (
+            word.to_lowercase(),
+            required.to_lowercase().next().unwrap_or(required),
+            extra.to_lowercase(),
+        )
+    } else {
+        (word.to_string(), required, extra.to_string())
+    };
+
+    match evaluate_guess(&word, required, &extra) {
+        GuessResult::TooShort => format!("shorter than {} letters", MIN_WORD_LENGTH),
+        GuessResult::IllegalLetter(c) => format!("'{}' is not a puzzle letter", c),
+        GuessResult::MissingRequired => format!("does not use the required letter '{}'", required),
+        GuessResult::Valid(_) => "not an answer".to_string(),
+    }
+}
+
+/// Print statistics about each dictionary in `paths`. If any of the
+/// dictionaries cannot be read an error naming the dictionary is printed and
+/// the program exits.
+fn validate_dicts(out: &mut dyn Write, paths: &[PathBuf], options: DictOptions) -> io::Result<()> {
+    for path in paths {
+        let dict =
+            open_dict_with(path, options).unwrap_or_else(|err| exit_dict_error(path, err.into()));
+        let stats = dictionary_stats(dict).unwrap_or_else(|err| exit_dict_error(path, err.into()));
+
+        writeln!(out, "{}:", path.display())?;
+        writeln!(out, "  lines: {}", stats.lines)?;
+        writeln!(out, "  blank lines: {}", stats.blank_lines)?;
+        writeln!(
+            out,
+            "  invalid UTF-8 lines: {}",
+            line_list(&stats.invalid_utf8_lines)
+        )?;
+        writeln!(
+            out,
+            "  entries with non-letters: {}",
+            stats.non_letter_words
+        )?;
+        writeln!(
+            out,
+            "  shortest word: {}",
+            stats.shortest.as_deref().unwrap_or("none")
+        )?;
+        writeln!(
+            out,
+            "  longest word: {}",
+            stats.longest.as_deref().unwrap_or("none")
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Print a puzzle made from a random pangram in the dictionaries, chosen using
+/// `seed` when it is given. The answers are printed after the puzzle if
+/// `answers` is true. If the dictionaries have no words with seven different
+/// letters an error is printed and the program exits.
+fn generate_puzzle(
+    out: &mut dyn Write,
+    args: &CliParams,
+    seed: Option<u64>,
+    answers: bool,
+) -> io::Result<()> {
+    let words = load_dicts(&args.dict_paths, DictOptions::from_args(args));
+    let candidates = find_pangram_candidates(&words);
+
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+
+    let Some((required, extra)) = choose_puzzle(&candidates, seed) else {
+        eprintln!(
+            "{} error: The dictionary has no words with seven different letters",
+            APP_SHORT_NAME
+        );
+        std::process::exit(1);
+    };
+
+    writeln!(out, "{} {}", required, extra)?;
+
+    if answers {
+        let found = if args.ignore_case {
+            find_all_ci(&words, required, &extra)
+        } else {
+            find_all(&words, required, &extra)
+        };
+
+        print_answers(out, args, required, &extra, found)?;
+    }
+
+    Ok(())
+}
+
+/// Pick a random word from the pangram `candidates` and one of its letters to
+/// be the required letter, using a random number generator seeded with `seed`.
+/// The letters are lowercased and the extra letters are returned in sorted
+/// order. Returns `None` when there are no candidates.
+fn choose_puzzle(candidates: &[String], seed: u64) -> Option<(char, String)> {
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let mut rng = SplitMix64(seed);
+    let word = &candidates[rng.below(candidates.len())];
+
+    let mut letters: Vec<char> = word.to_lowercase().chars().collect();
+    letters.sort_unstable();
+    letters.dedup();
+
+    let required = letters.remove(rng.below(letters.len()));
+    Some((required, letters.into_iter().collect()))
+}
+
+/// Small pseudo random number generator (splitmix64), which is plenty for
+/// picking a puzzle and gives the same numbers for the same seed everywhere.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Get the next random number.
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Get a random number less than `n`, which must not be zero.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Format a list of line numbers for printing, or `none` when it is empty.
+fn line_list(lines: &[usize]) -> String {
+    if lines.is_empty() {
+        "none".to_string()
+    } else {
+        let lines: Vec<String> = lines.iter().map(|n| n.to_string()).collect();
+        lines.join(", ")
+    }
+}
+
+/// Filter and sort the `answers` to a puzzle with the `required` letter
+/// according to the command line options, and then print them in the requested
+/// format to `out`.
+fn print_answers(
+    out: &mut dyn Write,
+    args: &CliParams,
+    required: char,
+    extra: &str,
+    mut answers: Vec<Answer>,
+) -> io::Result<()> {
+    let scoring = scoring(args);
+
+    // Rescore the answers when letters are worth extra points, so the minimum
+    // score and every other option use the adjusted scores.
+    if !scoring.letter_bonus.is_empty() {
+        for ans in &mut answers {
+            ans.score = score_breakdown(ans, &scoring).total;
         }
-        Err(err) => {
+    }
+
+    let mut bonuses = HashMap::new();
+
+    if let Some(path) = &args.bonus_file {
+        bonuses = load_bonuses(path).unwrap_or_else(|err| {
             eprintln!(
-                "{} error: Failed to load dictionary ({:?})",
-                APP_SHORT_NAME, err
+                "{} error: Failed to load bonus file {} ({})",
+                APP_SHORT_NAME,
+                path.display(),
+                err
             );
+            std::process::exit(1);
+        });
+
+        apply_bonuses(&mut answers, &bonuses);
+    }
+
+    if args.difficulty {
+        return writeln!(out, "{}", difficulty(&answers));
+    }
+
+    // The genius rank depends on every answer, including ones already found.
+    let genius = genius_threshold(&answers);
+
+    if let Some(path) = &args.exclude {
+        answers = exclude_words(answers, path, args.ignore_case);
+    }
+
+    let mut new_count = None;
+
+    if let Some(path) = &args.diff {
+        answers = new_answers(answers, path, args.ignore_case);
+        new_count = Some(answers.len());
+    }
+
+    if let Some(score) = args.current_score {
+        answers = words_to_reach(&answers, score, genius)
+            .into_iter()
+            .cloned()
+            .collect();
+    }
+
+    if args.pangrams {
+        answers.retain(|a| a.is_pangram);
+    }
+
+    if let Some(n) = args.required_count {
+        if args.ignore_case {
+            let required = required.to_lowercase().next().unwrap_or(required);
+            answers.retain(|a| count_char(&a.word.to_lowercase(), required) == n);
+        } else {
+            answers.retain(|a| count_char(&a.word, required) == n);
+        }
+    }
+
+    if let Some(len) = args.exact_length {
+        answers = filter_length(&answers, len).into_iter().cloned().collect();
+    }
+
+    if let Some(prefix) = &args.starts_with {
+        let found = if args.ignore_case {
+            filter_prefix_ci(&answers, prefix)
+        } else {
+            filter_prefix(&answers, prefix)
+        };
+
+        answers = found.into_iter().cloned().collect();
+    }
+
+    let mut answers = filter_answers(answers, args.min_score, args.top);
+
+    if args.count {
+        return writeln!(out, "{}", answers.len());
+    }
+
+    match args.tie_break {
+        Some(tie_break) => sort_answers(
+            &mut answers,
+            &SortOptions {
+                primary: args.sort.into(),
+                tie_break: tie_break.into(),
+            },
+        ),
+        None => sort_answers_by(&mut answers, args.sort.into()),
+    }
+
+    match args.format {
+        OutputFormat::Text => {
+            let style = LineStyle {
+                marker: &args.marker,
+                width: score_width(&answers),
+                color: args.color.enabled(args.output.is_none()),
+                letters: args
+                    .letters_used
+                    .then(|| PuzzleLetters::new(required, extra, args.ignore_case)),
+            };
+
+            if args.hints {
+                print_hints(out, &answers)?;
+            } else if args.explain {
+                print_explained(out, &answers, &scoring, &bonuses)?;
+            } else if args.anagrams {
+                print_anagrams(out, &answers)?;
+            } else if args.by_length {
+                print_by_length(out, &answers, &style)?;
+            } else if args.sort == SortMode::Score && args.tie_break.is_none() {
+                print_text(out, &answers, &style)?;
+            } else {
+                for ans in &answers {
+                    writeln!(out, "{}", answer_line(ans, &style))?;
+                }
+            }
+
+            if !args.no_summary {
+                if let Some(count) = new_count {
+                    writeln!(
+                        out,
+                        "{} new {}",
+                        count,
+                        plural(count as i32, "word", "words")
+                    )?;
+                }
+
+                print_summary(out, &answers)?;
+            }
+        }
+        #[cfg(feature = "serde")]
+        OutputFormat::Json => writeln!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&answers).expect("Failed to serialize answers")
+        )?,
+        OutputFormat::Tsv => print_tsv(out, &answers)?,
+    }
+
+    Ok(())
+}
+
+/// Print the answers as tab separated values, starting with a header row. The
+/// columns are always the word, its score and whether it is a pangram.
+fn print_tsv(out: &mut dyn Write, answers: &[Answer]) -> io::Result<()> {
+    writeln!(out, "word\tscore\tis_pangram")?;
+
+    for ans in answers {
+        writeln!(out, "{}\t{}\t{}", ans.word, ans.score, ans.is_pangram)?;
+    }
+
+    Ok(())
+}
+
+/// Find all valid answers in every dictionary listed in `paths`. Words found in
+/// more than one dictionary are only returned once. The dictionaries are read
+/// using `options`. If any of the dictionaries cannot be loaded an error naming
+/// the dictionary is printed and the program exits.
+fn find_all_in_dicts(
+    paths: &[PathBuf],
+    required: char,
+    extra: &str,
+    ignore_case: bool,
+    options: DictOptions,
+) -> Vec<Answer> {
+    let mut answers: Vec<Answer> = Vec::new();
+
+    for path in paths {
+        match open_dict_with(path, options)
+            .map_err(Error::from)
+            .and_then(|dict| find_all_with_dict(dict, required, extra, ignore_case))
+        {
+            Ok(found) => answers = merge_answers(answers, found),
+            Err(err) => exit_dict_error(path, err),
         }
+    }
+
+    answers
+}
+
+/// Check each word in the dictionaries as it is read and print it right away
+/// if it is an answer. Words are checked with the same `check_word` and
+/// `check_word_ci` functions used by `find_all`, and answers found in more than
+/// one dictionary are only printed once. If a dictionary cannot be read an
+/// error naming the dictionary is printed and the program exits.
+fn stream_answers(
+    out: &mut dyn Write,
+    args: &CliParams,
+    required: char,
+    extra: &str,
+) -> io::Result<()> {
+    let check = if args.ignore_case {
+        check_word_ci
+    } else {
+        check_word
     };
+
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for path in &args.dict_paths {
+        let dict = open_dict_with(path, DictOptions::from_args(args))
+            .unwrap_or_else(|err| exit_dict_error(path, err.into()));
+
+        for (index, line) in dict.lines().enumerate() {
+            let line = line.unwrap_or_else(|err| match err.kind() {
+                io::ErrorKind::InvalidData => {
+                    exit_dict_error(path, Error::Decode(index + 1, err.to_string()))
+                }
+                _ => exit_dict_error(path, err.into()),
+            });
+
+            // Skip the byte order mark some editors put at the start of files.
+            let line = match index {
+                0 => line.strip_prefix('\u{feff}').unwrap_or(&line),
+                _ => &line,
+            };
+            let word = line.trim();
+
+            if !is_candidate_word(word) {
+                continue;
+            }
+
+            if let Some(ans) = check(word, required, extra) {
+                if seen.insert(ans.word.clone()) {
+                    writeln!(
+                        out,
+                        "{}",
+                        format_answer(&ans, &args.marker, MIN_SCORE_WIDTH)
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
 }
 
-/// Find all valid answers given a path to a dictionary file specified by `path`.
-/// It is expected that the dictionary file contains one word per line.
-fn find_all_with_dict<P: AsRef<Path>>(
-    path: P,
+/// Read every word in the dictionaries listed in `paths`. Words found in more
+/// than one dictionary are only returned once. The dictionaries are read using
+/// `options`. If any of the dictionaries cannot be loaded an error naming the
+/// dictionary is printed and the program exits.
+fn load_dicts(paths: &[PathBuf], options: DictOptions) -> Vec<String> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut words: Vec<String> = Vec::new();
+
+    for path in paths {
+        match open_dict_with(path, options)
+            .map_err(Error::from)
+            .and_then(read_dict)
+        {
+            Ok(found) => words.extend(found.into_iter().filter(|w| seen.insert(w.clone()))),
+            Err(err) => exit_dict_error(path, err),
+        }
+    }
+
+    words
+}
+
+/// Print an error for a dictionary that could not be loaded and exit the
+/// program.
+fn exit_dict_error(path: &Path, err: Error) -> ! {
+    eprintln!(
+        "{} error: Failed to load dictionary {} ({})",
+        APP_SHORT_NAME,
+        path.display(),
+        err
+    );
+    std::process::exit(1);
+}
+
+/// Load the dictionaries once, and then solve each puzzle read from standard
+/// input until there is no more input. Lines that are not valid puzzles print
+/// an error and are otherwise ignored.
+fn run_interactive(out: &mut dyn Write, args: &CliParams) -> io::Result<()> {
+    if args.dict_paths.iter().any(|p| p == Path::new("-")) {
+        eprintln!(
+            "{} error: The dictionary cannot be read from standard input in interactive mode",
+            APP_SHORT_NAME
+        );
+        std::process::exit(1);
+    }
+
+    let index = DictionaryIndex::new(load_dicts(&args.dict_paths, DictOptions::from_args(args)));
+    let stdin = io::stdin();
+
+    loop {
+        eprint!("> ");
+
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(err) => {
+                eprintln!("{} error: {}", APP_SHORT_NAME, err);
+                break;
+            }
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match parse_line(&line) {
+            Ok((required, extra)) => {
+                let answers = solve_puzzle(&index, required, &extra, args.ignore_case);
+                print_answers(out, args, required, &extra, answers)?;
+
+                // Answers are written to a file in one go, so flush each
+                // puzzle's answers before waiting for the next one.
+                out.flush()?;
+            }
+            Err(err) => eprintln!("{} error: {}", APP_SHORT_NAME, err),
+        }
+    }
+
+    Ok(())
+}
+
+/// Error shown for interactive input that does not look like a puzzle.
+const INTERACTIVE_USAGE: &str =
+    "Expected the puzzle letters, or a required letter followed by the extra letters";
+
+/// Parse the puzzle on a line of interactive input. The line holds either all
+/// of the puzzle letters with the required letter first, or the required letter
+/// and the extra letters separated by whitespace.
+fn parse_line(line: &str) -> Result<(char, String), String> {
+    let invalid = |err: PuzzleError| format!("Invalid puzzle ({})", err);
+
+    match line.split_whitespace().collect::<Vec<_>>()[..] {
+        [puzzle] => parse_puzzle(puzzle).map_err(invalid),
+        [required, extra] => {
+            let mut chars = required.chars();
+            let required = match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => return Err(format!("'{}' is not a single required letter", required)),
+            };
+
+            match validate_puzzle(required, extra) {
+                Ok(()) | Err(PuzzleError::RequiredInExtra(_)) => Ok((required, extra.to_string())),
+                Err(err) => Err(invalid(err)),
+            }
+        }
+        _ => Err(INTERACTIVE_USAGE.to_string()),
+    }
+}
+
+/// Find all answers to a puzzle read in interactive mode using the dictionary
+/// `index`.
+fn solve_puzzle(
+    index: &DictionaryIndex,
     required: char,
     extra: &str,
-) -> std::io::Result<Vec<Answer>> {
-    let raw_file = File::open(path)?;
-    let file = BufReader::new(raw_file);
+    ignore_case: bool,
+) -> Vec<Answer> {
+    if ignore_case {
+        find_all_ci(index.words(), required, extra)
+    } else {
+        index.find_all(required, extra)
+    }
+}
 
-    Ok(find_all(
-        file.lines()
-            .map(|maybe_line| maybe_line.expect("Failed to read line from dictionary")),
-        required,
-        extra,
+/// Get the required and extra letters of the puzzle from the command line, and
+/// exit with an error if they do not make a valid puzzle.
+fn puzzle_letters(args: &CliParams) -> (char, String) {
+    if let Some(puzzle) = &args.puzzle {
+        return parse_puzzle(&sanitize_letters(puzzle))
+            .unwrap_or_else(|err| exit_invalid_puzzle(err));
+    }
+
+    if args.clipboard {
+        let text = read_clipboard().unwrap_or_else(|err| {
+            eprintln!(
+                "{} error: Failed to read the clipboard ({})",
+                APP_SHORT_NAME, err
+            );
+            std::process::exit(1);
+        });
+
+        return parse_puzzle(&sanitize_letters(&text)).unwrap_or_else(|err| {
+            eprintln!(
+                "{} error: The clipboard does not hold a puzzle ({})",
+                APP_SHORT_NAME, err
+            );
+            std::process::exit(1);
+        });
+    }
+
+    // The puzzle letters are optional on the command line when a config file
+    // is given, so they might still be missing.
+    let (required, extra) = match (args.required_char, &args.extra_chars) {
+        (Some(required), Some(extra)) => (required, extra.clone()),
+        _ => CliParams::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "The required and extra characters were not given on the command line or in the config file",
+            )
+            .exit(),
+    };
+
+    // Refuse to solve puzzles with letters that can never be valid, but allow
+    // the minor mistake of repeating the required letter as an extra letter.
+    // Puzzles without extra letters are also allowed even though they are
+    // unlikely to be what was meant.
+    match validate_puzzle(required, &extra) {
+        Ok(()) => {}
+        Err(err @ PuzzleError::RequiredInExtra(_)) => {
+            eprintln!("{} warning: {}", APP_SHORT_NAME, err);
+        }
+        Err(PuzzleError::EmptyExtra) => {
+            eprintln!(
+                "{} warning: no extra letters were given, so only words spelled with just '{}' are answers and every answer is a pangram",
+                APP_SHORT_NAME, required
+            );
+        }
+        Err(err) => exit_invalid_puzzle(err),
+    }
+
+    (required, extra)
+}
+
+/// Get the text currently on the system clipboard.
+#[cfg(feature = "clipboard")]
+fn read_clipboard() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|err| err.to_string())
+}
+
+/// The clipboard cannot be read unless the `clipboard` feature is enabled.
+#[cfg(not(feature = "clipboard"))]
+fn read_clipboard() -> Result<String, String> {
+    Err("clipboard support was not enabled when spellingbee was built".to_string())
+}
+
+/// Print an invalid puzzle error and exit the program.
+fn exit_invalid_puzzle(err: PuzzleError) -> ! {
+    eprintln!("{} error: Invalid puzzle ({})", APP_SHORT_NAME, err);
+    std::process::exit(1);
+}
+
+/// Minimum width of the score column so one and two digit scores line up.
+const MIN_SCORE_WIDTH: usize = 2;
+
+/// Get the width of the score column needed to line up every answer's score.
+fn score_width(answers: &[Answer]) -> usize {
+    answers
+        .iter()
+        .map(|a| a.score.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max(MIN_SCORE_WIDTH)
+}
+
+/// Format an answer as a line with the pangram `marker` (or matching blank
+/// space), the score padded to `width` characters and then the word.
+fn format_answer(ans: &Answer, marker: &str, width: usize) -> String {
+    let marker_width = marker.chars().count();
+    let marker = if ans.is_pangram { marker } else { "" };
+
+    format!(
+        "{:marker_width$} {:<width$} {}",
+        marker,
+        ans.score,
+        ans.word,
+        marker_width = marker_width,
+        width = width
+    )
+}
+
+/// How answers are printed as lines of text.
+struct LineStyle<'a> {
+    /// Text printed in front of pangrams.
+    marker: &'a str,
+    /// Number of characters the score is padded to.
+    width: usize,
+    /// Highlight pangrams with color.
+    color: bool,
+    /// Puzzle letters used to show how many of them each answer uses, or
+    /// `None` to leave that column out.
+    letters: Option<PuzzleLetters>,
+}
+
+/// The letters of a puzzle, lowercased when letter case is ignored.
+struct PuzzleLetters {
+    required: char,
+    extra: String,
+    ignore_case: bool,
+}
+
+impl PuzzleLetters {
+    fn new(required: char, extra: &str, ignore_case: bool) -> PuzzleLetters {
+        if ignore_case {
+            PuzzleLetters {
+                required: required.to_lowercase().next().unwrap_or(required),
+                extra: extra.to_lowercase(),
+                ignore_case,
+            }
+        } else {
+            PuzzleLetters {
+                required,
+                extra: extra.to_string(),
+                ignore_case,
+            }
+        }
+    }
+
+    /// Count how many different puzzle letters `word` uses.
+    fn used_by(&self, word: &str) -> usize {
+        if self.ignore_case {
+            distinct_puzzle_letters(&word.to_lowercase(), self.required, &self.extra)
+        } else {
+            distinct_puzzle_letters(word, self.required, &self.extra)
+        }
+    }
+}
+
+/// Format an answer the same as `format_answer`, followed by the number of
+/// puzzle letters it uses when `style` has letters. The line is highlighted
+/// when it is a pangram and `style` uses color.
+fn answer_line(ans: &Answer, style: &LineStyle) -> String {
+    let mut line = format_answer(ans, style.marker, style.width);
+
+    if let Some(letters) = &style.letters {
+        line += &format!(" ({} letters)", letters.used_by(&ans.word));
+    }
+
+    if style.color && ans.is_pangram {
+        highlight(&line)
+    } else {
+        line
+    }
+}
+
+/// Wrap `text` in the ANSI escape codes for bold yellow text.
+fn highlight(text: &str) -> String {
+    format!("\x1b[1;33m{}\x1b[0m", text)
+}
+
+/// Print answers as aligned columns of score and word. Pangrams are printed
+/// before all other answers and are marked with `marker`.
+fn print_text(out: &mut dyn Write, answers: &[Answer], style: &LineStyle) -> io::Result<()> {
+    for ans in answers.iter().filter(|&a| a.is_pangram) {
+        writeln!(out, "{}", answer_line(ans, style))?;
+    }
+
+    for ans in answers.iter().filter(|&a| !a.is_pangram) {
+        writeln!(out, "{}", answer_line(ans, style))?;
+    }
+
+    Ok(())
+}
+
+/// Print answers in sections of words with the same length, from shortest to
+/// longest. Words are listed alphabetically within each section.
+fn print_by_length(out: &mut dyn Write, answers: &[Answer], style: &LineStyle) -> io::Result<()> {
+    for (len, group) in group_by_length(answers) {
+        writeln!(out, "{} letters:", len)?;
+
+        for ans in group {
+            writeln!(out, "{}", answer_line(ans, style))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Print every word in `words` that is one mistake away from being an answer.
+/// Letter case is ignored when `ignore_case` is true.
+fn print_near_misses(
+    out: &mut dyn Write,
+    words: &[String],
+    required: char,
+    extra: &str,
+    ignore_case: bool,
+) -> io::Result<()> {
+    writeln!(out, "Near misses:")?;
+
+    let (required, extra) = if ignore_case {
+        (
+            required.to_lowercase().next().unwrap_or(required),
+            extra.to_lowercase(),
+        )
+    } else {
+        (required, extra.to_string())
+    };
+
+    for word in words {
+        let misses = if ignore_case {
+            near_misses(&word.to_lowercase(), required, &extra)
+        } else {
+            near_misses(word, required, &extra)
+        };
+
+        if misses == Some(1) {
+            writeln!(out, "  {}", word)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Print each group of answers that are anagrams of each other on its own
+/// line. Answers without any anagrams are not printed.
+fn print_anagrams(out: &mut dyn Write, answers: &[Answer]) -> io::Result<()> {
+    for group in group_anagrams(answers).into_iter().filter(|g| g.len() > 1) {
+        let words: Vec<&str> = group.iter().map(|a| a.word.as_str()).collect();
+        writeln!(out, "{}", words.join(", "))?;
+    }
+
+    Ok(())
+}
+
+/// Print each answer along with how its score was calculated, for example
+/// "motel: 5 (length) + 7 (pangram) = 12".
+fn print_explained(
+    out: &mut dyn Write,
+    answers: &[Answer],
+    scoring: &Scoring,
+    bonuses: &HashMap<String, i32>,
+) -> io::Result<()> {
+    for ans in answers {
+        writeln!(out, "{}", explain_answer(ans, scoring, bonuses))?;
+    }
+
+    Ok(())
+}
+
+/// Describe how the score of `ans` was calculated, including any points from
+/// the bonus file.
+fn explain_answer(ans: &Answer, scoring: &Scoring, bonuses: &HashMap<String, i32>) -> String {
+    let breakdown = score_breakdown(ans, scoring);
+    let base_reason = if ans.word.chars().count() >= scoring.long_word_min_len {
+        "length"
+    } else {
+        "short word"
+    };
+
+    let mut line = format!("{}: {} ({})", ans.word, breakdown.base, base_reason);
+
+    if breakdown.pangram_bonus != 0 {
+        line += &format!(" + {} (pangram)", breakdown.pangram_bonus);
+    }
+
+    if breakdown.letter_bonus != 0 {
+        line += &format!(" + {} (letters)", breakdown.letter_bonus);
+    }
+
+    let file_bonus = bonuses.get(&ans.word).copied().unwrap_or(0);
+
+    if file_bonus != 0 {
+        line += &format!(" + {} (bonus file)", file_bonus);
+    }
+
+    line + &format!(" = {}", breakdown.total + file_bonus)
+}
+
+/// Print the number of answers starting with each two letter prefix. Prefixes
+/// that start with the same letter are printed on the same line.
+fn print_hints(out: &mut dyn Write, answers: &[Answer]) -> io::Result<()> {
+    let counts = two_letter_counts(answers);
+    let mut first_letters: Vec<char> = counts.keys().filter_map(|p| p.chars().next()).collect();
+    first_letters.dedup();
+
+    for first in first_letters {
+        let line: Vec<String> = counts
+            .iter()
+            .filter(|(prefix, _)| prefix.starts_with(first))
+            .map(|(prefix, count)| format!("{}: {}", prefix, count))
+            .collect();
+
+        writeln!(out, "{}", line.join(", "))?;
+    }
+
+    Ok(())
+}
+
+/// Print the number of answers and pangrams in `answers`, along with the total
+/// number of points they are worth.
+fn print_summary(out: &mut dyn Write, answers: &[Answer]) -> io::Result<()> {
+    let summary = summarize(answers);
+    writeln!(
+        out,
+        "Found {} {} ({} {}) for a total of {} {}",
+        summary.word_count,
+        plural(summary.word_count as i32, "word", "words"),
+        summary.pangram_count,
+        plural(summary.pangram_count as i32, "pangram", "pangrams"),
+        summary.total_score,
+        plural(summary.total_score, "point", "points"),
+    )
+}
+
+/// Choose between the `singular` or `plural` form of a word for `count` items.
+fn plural<'a>(count: i32, singular: &'a str, plural: &'a str) -> &'a str {
+    if count == 1 {
+        singular
+    } else {
+        plural
+    }
+}
+
+/// Remove any answers listed in the word list at `path`. Words are compared
+/// without regard to case when `ignore_case` is true. If the word list cannot
+/// be loaded an error is printed and the program exits.
+fn exclude_words(answers: Vec<Answer>, path: &Path, ignore_case: bool) -> Vec<Answer> {
+    let mut exclude = load_word_set_or_exit(path);
+
+    if ignore_case {
+        exclude = exclude.iter().map(|w| w.to_lowercase()).collect();
+        answers
+            .into_iter()
+            .filter(|a| !exclude.contains(&a.word.to_lowercase()))
+            .collect()
+    } else {
+        remove_words(answers, &exclude)
+    }
+}
+
+/// Keep only the answers that are not listed in the previous answers file at
+/// `path`. Letter case is ignored when `ignore_case` is true. If the file
+/// cannot be read an error is printed and the program exits.
+fn new_answers(answers: Vec<Answer>, path: &Path, ignore_case: bool) -> Vec<Answer> {
+    let mut previous = load_word_set_or_exit(path);
+
+    if ignore_case {
+        previous = previous.iter().map(|w| w.to_lowercase()).collect();
+        answers
+            .into_iter()
+            .filter(|a| !previous.contains(&a.word.to_lowercase()))
+            .collect()
+    } else {
+        diff_answers(&answers, &previous)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+}
+
+/// Load a word list with `load_word_set`, or print an error and exit the
+/// program if it cannot be read.
+fn load_word_set_or_exit(path: &Path) -> HashSet<String> {
+    load_word_set(path).unwrap_or_else(|err| {
+        eprintln!(
+            "{} error: Failed to load word list {} ({})",
+            APP_SHORT_NAME,
+            path.display(),
+            err
+        );
+        std::process::exit(1);
+    })
+}
+
+/// Load a file with one word per line into a set of words, ignoring blank lines
+/// and any whitespace surrounding the words.
+fn load_word_set(path: &Path) -> io::Result<HashSet<String>> {
+    let mut words = HashSet::new();
+
+    for line in open_dict(path, false)?.lines() {
+        let line = line?;
+        let word = line.trim();
+
+        if !word.is_empty() {
+            words.insert(word.to_string());
+        }
+    }
+
+    Ok(words)
+}
+
+/// Load a file of `word=points` lines into a map of bonus points for each word.
+/// Blank lines are ignored, and any other line that is not a word followed by
+/// `=` and a number is an error.
+fn load_bonuses(path: &Path) -> io::Result<HashMap<String, i32>> {
+    let mut bonuses = HashMap::new();
+
+    for (index, line) in open_dict(path, false)?.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let bonus = line.split_once('=').and_then(|(word, points)| {
+            let points = points.trim().parse::<i32>().ok()?;
+            Some((word.trim().to_string(), points))
+        });
+
+        match bonus {
+            Some((word, points)) if !word.is_empty() => {
+                bonuses.insert(word, points);
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: expected `word=points`", index + 1),
+                ))
+            }
+        }
+    }
+
+    Ok(bonuses)
+}
+
+/// Open the dictionary file at `path` for reading. A path of `-` reads the
+/// dictionary from standard input instead of a file.
+fn open_dict<P: AsRef<Path>>(path: P, gzip: bool) -> io::Result<Box<dyn BufRead>> {
+    let options = DictOptions {
+        gzip,
+        ..DictOptions::default()
+    };
+
+    open_dict_with(path, options)
+}
+
+/// Options for reading dictionaries given on the command line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct DictOptions {
+    /// Decompress every dictionary with gzip.
+    gzip: bool,
+    /// Show a progress indicator while reading when standard error is a
+    /// terminal.
+    progress: bool,
+    /// Read the words from this column, starting at 1, of comma separated
+    /// lines.
+    csv_column: Option<usize>,
+}
+
+impl DictOptions {
+    fn from_args(args: &CliParams) -> DictOptions {
+        DictOptions {
+            gzip: args.gzip,
+            progress: args.progress,
+            csv_column: args.csv_column,
+        }
+    }
+}
+
+/// Version of `open_dict` that reads the dictionary using `options`.
+fn open_dict_with<P: AsRef<Path>>(path: P, options: DictOptions) -> io::Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    let mut len = None;
+    let mut reader: Box<dyn Read> = if path == Path::new("-") {
+        Box::new(io::stdin())
+    } else if let Some(words) = bundled_words(path) {
+        Box::new(words.as_bytes())
+    } else {
+        let file = File::open(path)?;
+        len = file.metadata().ok().map(|m| m.len());
+        Box::new(file)
+    };
+
+    // Progress is measured before decompression so it matches the file size.
+    if progress::enabled(options.progress) {
+        reader = progress::track(reader, len);
+    }
+
+    let dict = if options.gzip || path.extension().is_some_and(|ext| ext == "gz") {
+        gzip_decoder(reader)?
+    } else {
+        Box::new(BufReader::new(reader))
+    };
+
+    Ok(match options.csv_column {
+        Some(column) => Box::new(CsvColumn::new(dict, column)),
+        None => dict,
+    })
+}
+
+/// Reader that turns each comma separated line read from `inner` into a line
+/// holding only the field in one column. Lines without that column become
+/// empty lines.
+struct CsvColumn<R> {
+    inner: R,
+    /// Index of the column to keep, starting at 0.
+    index: usize,
+    /// The current line after it was cut down to one column.
+    line: Vec<u8>,
+    /// How much of `line` has been consumed.
+    pos: usize,
+}
+
+impl<R: BufRead> CsvColumn<R> {
+    /// Read column `column` (starting at 1) of every line in `inner`.
+    fn new(inner: R, column: usize) -> CsvColumn<R> {
+        CsvColumn {
+            inner,
+            index: column.saturating_sub(1),
+            line: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Read for CsvColumn<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for CsvColumn<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.line.len() {
+            let mut line = Vec::new();
+            self.pos = 0;
+            self.line.clear();
+
+            // Fields are split on bytes so lines that are not valid UTF-8 are
+            // still reported when they are decoded.
+            if self.inner.read_until(b'\n', &mut line)? > 0 {
+                let line = line.strip_suffix(b"\n").unwrap_or(&line);
+                let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+                if let Some(field) = line.split(|&b| b == b',').nth(self.index) {
+                    self.line.extend_from_slice(field);
+                }
+
+                self.line.push(b'\n');
+            }
+        }
+
+        Ok(&self.line[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.line.len());
+    }
+}
+
+/// Get the bundled word list if `path` names it rather than a file.
+#[cfg(feature = "bundled-dict")]
+fn bundled_words(path: &Path) -> Option<&'static str> {
+    (path == Path::new(BUNDLED_DICT_PATH)).then_some(spellingbee::BUNDLED_WORDS)
+}
+
+/// Every path is a file without the `bundled-dict` feature.
+#[cfg(not(feature = "bundled-dict"))]
+fn bundled_words(_path: &Path) -> Option<&'static str> {
+    None
+}
+
+/// Wrap `reader` so the gzip compressed data it returns is decompressed.
+#[cfg(feature = "gzip")]
+fn gzip_decoder(reader: Box<dyn Read>) -> io::Result<Box<dyn BufRead>> {
+    Ok(Box::new(BufReader::new(flate2::read::GzDecoder::new(
+        reader,
+    ))))
+}
+
+/// Gzip compressed dictionaries cannot be read unless the `gzip` feature is
+/// enabled.
+#[cfg(not(feature = "gzip"))]
+fn gzip_decoder(_reader: Box<dyn Read>) -> io::Result<Box<dyn BufRead>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "gzip support was not enabled when spellingbee was built",
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write `contents` to a uniquely named file in the temp directory.
+    fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("spellingbee-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[cfg(feature = "gzip")]
+    fn gzip(contents: &[u8]) -> Vec<u8> {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(contents).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn gzip_dictionary_finds_same_answers() {
+        let dict = b"tote\nvote\nmotel\nsoapy\ntome\n";
+        let path = temp_file("words.gz", &gzip(dict));
+
+        let expected = find_all_with_dict(&dict[..], 't', "elom", false).unwrap();
+        let dict = open_dict(&path, false).unwrap();
+        let answers = find_all_with_dict(dict, 't', "elom", false).unwrap();
+
+        assert_eq!(expected, answers);
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn gzip_flag_decompresses_without_extension() {
+        let path = temp_file("words-compressed", &gzip(b"tote\nmotel\n"));
+        let dict = open_dict(&path, true).unwrap();
+        let answers = find_all_with_dict(dict, 't', "elom", false).unwrap();
+
+        assert_eq!(2, answers.len());
+    }
+
+    #[test]
+    #[cfg(not(feature = "gzip"))]
+    fn gzip_dictionary_needs_gzip_feature() {
+        let path = temp_file("unsupported.gz", b"");
+        let result = open_dict(&path, false);
+
+        assert_eq!(io::ErrorKind::Unsupported, result.err().unwrap().kind());
+    }
+
+    fn answer(word: &str, score: i32, is_pangram: bool) -> Answer {
+        Answer {
+            word: word.to_string(),
+            score,
+            is_pangram,
+        }
+    }
+
+    #[test]
+    fn format_answer_matches_display() {
+        let pangram = answer("motel", 12, true);
+        let other = answer("tome", 1, false);
+
+        assert_eq!(pangram.to_string(), format_answer(&pangram, "*", 2));
+        assert_eq!(other.to_string(), format_answer(&other, "*", 2));
+    }
+
+    #[test]
+    fn format_answer_aligns_three_digit_scores() {
+        let answers = vec![answer("pangram", 107, true), answer("tome", 1, false)];
+        let width = score_width(&answers);
+
+        assert_eq!(3, width);
+        assert_eq!("* 107 pangram", format_answer(&answers[0], "*", width));
+        assert_eq!("  1   tome", format_answer(&answers[1], "*", width));
+    }
+
+    #[test]
+    fn format_answer_pads_custom_marker() {
+        let pangram = answer("motel", 12, true);
+        let other = answer("tome", 1, false);
+
+        assert_eq!("[P] 12 motel", format_answer(&pangram, "[P]", 2));
+        assert_eq!("    1  tome", format_answer(&other, "[P]", 2));
+    }
+
+    #[test]
+    fn score_width_has_minimum() {
+        assert_eq!(MIN_SCORE_WIDTH, score_width(&[]));
+        assert_eq!(MIN_SCORE_WIDTH, score_width(&[answer("tome", 1, false)]));
+    }
+
+    #[test]
+    fn explain_short_word() {
+        let ans = answer("tome", 1, false);
+        assert_eq!(
+            "tome: 1 (short word) = 1",
+            explain_answer(&ans, &Scoring::default(), &HashMap::new())
+        );
+    }
+
+    #[test]
+    fn explain_pangram() {
+        let ans = answer("motel", 12, true);
+        assert_eq!(
+            "motel: 5 (length) + 7 (pangram) = 12",
+            explain_answer(&ans, &Scoring::default(), &HashMap::new())
+        );
+    }
+
+    #[test]
+    fn explain_bonus_file_points() {
+        let ans = answer("tome", 5, false);
+        let bonuses: HashMap<String, i32> = [("tome".to_string(), 4)].into_iter().collect();
+        assert_eq!(
+            "tome: 1 (short word) + 4 (bonus file) = 5",
+            explain_answer(&ans, &Scoring::default(), &bonuses)
+        );
+    }
+
+    fn index_of(list: &[&str]) -> DictionaryIndex {
+        DictionaryIndex::new(list)
+    }
+
+    #[test]
+    fn parse_line_with_combined_letters() {
+        assert_eq!(Ok(('t', "elomab".to_string())), parse_line("telomab\n"));
+    }
+
+    #[test]
+    fn parse_line_with_required_and_extra() {
+        assert_eq!(Ok(('t', "elom".to_string())), parse_line("  t elom \n"));
+    }
+
+    #[test]
+    fn parse_line_rejects_malformed_input() {
+        assert!(parse_line("tel").is_err());
+        assert!(parse_line("te lom").is_err());
+        assert!(parse_line("t el0m").is_err());
+        assert!(parse_line("t e l o m").is_err());
+    }
+
+    #[test]
+    fn solve_puzzle_finds_answers() {
+        let index = index_of(&["tote", "motel", "vote"]);
+        let answers = solve_puzzle(&index, 't', "elom", false);
+        assert_eq!(2, answers.len());
+        assert_eq!("motel", answers[1].word);
+    }
+
+    #[test]
+    fn solve_puzzle_ignores_case() {
+        let index = index_of(&["Tote", "MOTEL"]);
+        assert!(solve_puzzle(&index, 't', "elom", false).is_empty());
+        assert_eq!(2, solve_puzzle(&index, 't', "elom", true).len());
+    }
+
+    #[test]
+    #[cfg(feature = "config")]
+    fn parses_config_file() {
+        let config: Config =
+            toml::from_str("dict_path = \"/tmp/words.txt\"\nrequired = \"t\"\nextra = \"elom\"\n")
+                .unwrap();
+
+        assert_eq!(
+            Config {
+                dict_path: Some(PathBuf::from("/tmp/words.txt")),
+                required: Some('t'),
+                extra: Some("elom".to_string()),
+            },
+            config
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "config")]
+    fn config_file_keys_are_optional() {
+        let config: Config = toml::from_str("required = \"t\"\n").unwrap();
+        assert_eq!(Some('t'), config.required);
+        assert_eq!(None, config.dict_path);
+    }
+
+    #[test]
+    #[cfg(feature = "config")]
+    fn config_file_rejects_unknown_keys() {
+        assert!(toml::from_str::<Config>("dictionary = \"words\"\n").is_err());
+    }
+
+    fn sample_config() -> Config {
+        Config {
+            dict_path: Some(PathBuf::from("config-words.txt")),
+            required: Some('v'),
+            extra: Some("otelm".to_string()),
+        }
+    }
+
+    #[test]
+    fn config_fills_in_missing_options() {
+        let mut args = CliParams::parse_from(["spellingbee", "--config", "sb.toml"]);
+        sample_config().apply(&mut args);
+
+        assert_eq!(vec![PathBuf::from("config-words.txt")], args.dict_paths);
+        assert_eq!(Some('v'), args.required_char);
+        assert_eq!(Some("otelm".to_string()), args.extra_chars);
+    }
+
+    #[test]
+    fn command_line_overrides_config() {
+        let mut args = CliParams::parse_from([
+            "spellingbee",
+            "--config",
+            "sb.toml",
+            "-d",
+            "cli-words.txt",
+            "t",
+            "elom",
+        ]);
+        sample_config().apply(&mut args);
+
+        assert_eq!(vec![PathBuf::from("cli-words.txt")], args.dict_paths);
+        assert_eq!(Some('t'), args.required_char);
+        assert_eq!(Some("elom".to_string()), args.extra_chars);
+    }
+
+    #[test]
+    fn command_line_puzzle_overrides_config_letters() {
+        let mut args =
+            CliParams::parse_from(["spellingbee", "--config", "sb.toml", "--puzzle", "telomab"]);
+        sample_config().apply(&mut args);
+
+        assert_eq!(None, args.required_char);
+        assert_eq!(None, args.extra_chars);
+    }
+
+    #[test]
+    fn highlight_wraps_text_in_escape_codes() {
+        assert_eq!("\x1b[1;33mmotel\x1b[0m", highlight("motel"));
+    }
+
+    #[test]
+    fn answer_line_only_highlights_pangrams() {
+        let pangram = answer("motel", 12, true);
+        let other = answer("tome", 1, false);
+
+        let style = LineStyle {
+            marker: "*",
+            width: 2,
+            color: true,
+            letters: None,
+        };
+
+        assert_eq!(highlight("* 12 motel"), answer_line(&pangram, &style));
+        assert_eq!("  1  tome", answer_line(&other, &style));
+    }
+
+    #[test]
+    fn answer_line_without_color_is_plain() {
+        let pangram = answer("motel", 12, true);
+        let style = LineStyle {
+            marker: "*",
+            width: 2,
+            color: false,
+            letters: None,
+        };
+
+        assert_eq!("* 12 motel", answer_line(&pangram, &style));
+    }
+
+    #[test]
+    fn unix_default_dict_path() {
+        assert_eq!(
+            Some(PathBuf::from(UNIX_DICT_PATH)),
+            select_dict_path(Platform::Unix, |_| false, None)
+        );
+    }
+
+    #[test]
+    fn macos_default_dict_path_prefers_words() {
+        assert_eq!(
+            Some(PathBuf::from(UNIX_DICT_PATH)),
+            select_dict_path(Platform::MacOs, |_| true, None)
+        );
+    }
+
+    #[test]
+    fn macos_default_dict_path_falls_back_to_web2() {
+        let exists = |path: &Path| path == Path::new(MACOS_FALLBACK_DICT_PATH);
+
+        assert_eq!(
+            Some(PathBuf::from(MACOS_FALLBACK_DICT_PATH)),
+            select_dict_path(Platform::MacOs, exists, None)
+        );
+    }
+
+    #[test]
+    fn windows_default_dict_path_uses_env_var() {
+        let env_path = PathBuf::from("C:\\words.txt");
+
+        assert_eq!(
+            Some(env_path.clone()),
+            select_dict_path(Platform::Windows, |_| true, Some(env_path))
+        );
+        assert_eq!(None, select_dict_path(Platform::Windows, |_| true, None));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn current_platform_on_linux() {
+        assert_eq!(Platform::Unix, Platform::current());
+    }
+
+    #[test]
+    #[cfg(feature = "bundled-dict")]
+    fn open_dict_reads_bundled_words() {
+        let words = read_dict(open_dict(BUNDLED_DICT_PATH, false).unwrap()).unwrap();
+        assert_eq!(
+            read_dict(spellingbee::BUNDLED_WORDS.as_bytes()).unwrap(),
+            words
+        );
+    }
+
+    #[test]
+    fn answer_line_shows_letters_used() {
+        let style = LineStyle {
+            marker: "*",
+            width: 2,
+            color: false,
+            letters: Some(PuzzleLetters::new('t', "elom", false)),
+        };
+
+        assert_eq!(
+            "* 12 motel (5 letters)",
+            answer_line(&answer("motel", 12, true), &style)
+        );
+        assert_eq!(
+            "  1  tote (3 letters)",
+            answer_line(&answer("tote", 1, false), &style)
+        );
+    }
+
+    #[test]
+    fn letters_used_ignores_case() {
+        let letters = PuzzleLetters::new('T', "ELOM", true);
+        assert_eq!(3, letters.used_by("Tote"));
+    }
+
+    #[test]
+    fn csv_column_reads_one_field_per_line() {
+        let csv = "tote,vote\r\nmotel,soapy\nshort\n,tome\n";
+
+        let first = CsvColumn::new(csv.as_bytes(), 1);
+        assert_eq!(vec!["tote", "motel", "short"], read_dict(first).unwrap());
+
+        let second = CsvColumn::new(csv.as_bytes(), 2);
+        assert_eq!(vec!["vote", "soapy", "tome"], read_dict(second).unwrap());
+    }
+
+    #[test]
+    fn csv_column_keeps_line_numbers() {
+        let csv: &[u8] = b"word,note\ntote,ok\n\xff,bad\n";
+        let result = read_dict(CsvColumn::new(csv, 1));
+
+        assert!(matches!(result, Err(Error::Decode(3, _))));
+    }
+
+    #[test]
+    fn rejection_reasons() {
+        assert_eq!(
+            "shorter than 4 letters",
+            rejection_reason("tot", 't', "elom", false)
+        );
+        assert_eq!(
+            "'v' is not a puzzle letter",
+            rejection_reason("vote", 't', "elom", false)
+        );
+        assert_eq!(
+            "does not use the required letter 't'",
+            rejection_reason("mole", 't', "elom", false)
+        );
+        assert_eq!(
+            "'T' is not a puzzle letter",
+            rejection_reason("Tote", 't', "elom", false)
+        );
+        assert_eq!("not an answer", rejection_reason("Tote", 't', "elom", true));
+    }
+
+    #[test]
+    fn parses_letter_bonus() {
+        assert_eq!(Ok(('z', 5)), parse_letter_bonus("z=5"));
+        assert_eq!(Ok(('q', -2)), parse_letter_bonus(" q = -2 "));
+        assert!(parse_letter_bonus("z").is_err());
+        assert!(parse_letter_bonus("zz=5").is_err());
+        assert!(parse_letter_bonus("3=5").is_err());
+        assert!(parse_letter_bonus("z=five").is_err());
+    }
+
+    fn candidates() -> Vec<String> {
+        ["unpopular", "emoticon", "telomic"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn choose_puzzle_is_deterministic_for_seed() {
+        let expected = [
+            (0, 'e', "cimnot"),
+            (1, 'c', "eilmot"),
+            (42, 'o', "ceimnt"),
+            (u64::MAX, 'e', "cilmot"),
+        ];
+
+        for (seed, required, extra) in expected {
+            assert_eq!(
+                Some((required, extra.to_string())),
+                choose_puzzle(&candidates(), seed)
+            );
+        }
+    }
+
+    #[test]
+    fn choose_puzzle_uses_letters_of_a_candidate() {
+        for seed in 0..20 {
+            let (required, extra) = choose_puzzle(&candidates(), seed).unwrap();
+            let mut letters: Vec<char> = extra.chars().chain([required]).collect();
+            letters.sort_unstable();
+
+            assert_eq!(6, extra.chars().count());
+            assert!(validate_puzzle(required, &extra).is_ok());
+            assert!(candidates().iter().any(|w| {
+                let mut word: Vec<char> = w.chars().collect();
+                word.sort_unstable();
+                word.dedup();
+                word == letters
+            }));
+        }
+    }
+
+    #[test]
+    fn choose_puzzle_lowercases_letters() {
+        let (required, extra) = choose_puzzle(&["Unpopular".to_string()], 3).unwrap();
+        let mut letters: Vec<char> = extra.chars().chain([required]).collect();
+        letters.sort_unstable();
+
+        assert_eq!(vec!['a', 'l', 'n', 'o', 'p', 'r', 'u'], letters);
+    }
+
+    #[test]
+    fn choose_puzzle_without_candidates() {
+        assert_eq!(None, choose_puzzle(&[], 7));
+    }
+}
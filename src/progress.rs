@@ -0,0 +1,67 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (C) 2022 Scott MacDonald.
+////////////////////////////////////////////////////////////////////////////////
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+////////////////////////////////////////////////////////////////////////////////
+//! Progress indicator shown on standard error while large dictionaries are
+//! read by the command line tool.
+use std::io::{self, IsTerminal, Read};
+
+/// Test if a progress indicator should be shown. It is only shown when it was
+/// `requested` and standard error is a terminal, so piped or redirected output
+/// never contains it.
+pub fn enabled(requested: bool) -> bool {
+    requested && io::stderr().is_terminal()
+}
+
+/// Check that progress indicators can be shown, which needs the `progress`
+/// feature.
+#[cfg(feature = "progress")]
+pub fn supported() -> Result<(), String> {
+    Ok(())
+}
+
+/// Progress indicators cannot be shown unless the `progress` feature is
+/// enabled.
+#[cfg(not(feature = "progress"))]
+pub fn supported() -> Result<(), String> {
+    Err("progress support was not enabled when spellingbee was built".to_string())
+}
+
+/// Wrap `reader` so that reading from it updates a progress indicator. When
+/// the length of the dictionary is known a progress bar is shown, otherwise a
+/// spinner with the number of bytes read so far. The indicator is cleared once
+/// the reader is dropped.
+#[cfg(feature = "progress")]
+pub fn track(reader: Box<dyn Read>, len: Option<u64>) -> Box<dyn Read> {
+    use indicatif::{ProgressBar, ProgressFinish, ProgressStyle};
+
+    let bar = match len {
+        Some(len) => ProgressBar::new(len).with_style(
+            ProgressStyle::with_template("{bar:40} {bytes}/{total_bytes}")
+                .expect("invalid progress bar template"),
+        ),
+        None => ProgressBar::new_spinner().with_style(
+            ProgressStyle::with_template("{spinner} {bytes} read")
+                .expect("invalid progress spinner template"),
+        ),
+    };
+
+    Box::new(bar.with_finish(ProgressFinish::AndClear).wrap_read(reader))
+}
+
+/// Progress is never shown without the `progress` feature.
+#[cfg(not(feature = "progress"))]
+pub fn track(reader: Box<dyn Read>, _len: Option<u64>) -> Box<dyn Read> {
+    reader
+}
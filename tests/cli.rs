@@ -0,0 +1,985 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (C) 2022 Scott MacDonald.
+////////////////////////////////////////////////////////////////////////////////
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+////////////////////////////////////////////////////////////////////////////////
+#![cfg(feature = "std")]
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+const DICT: &str = "tote\nvote\nmotel\nsoapy\ntome\n";
+
+/// Run the spellingbee binary with `args`, writing `stdin` to its standard
+/// input.
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_spellingbee"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start spellingbee");
+
+    // The program may exit before reading all of its input, for example when
+    // the puzzle is invalid, so ignore any errors writing to its stdin.
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+
+    child.wait_with_output().unwrap()
+}
+
+/// Write `contents` to a uniquely named file in the temp directory.
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("spellingbee-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn stdout_of(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn reads_dictionary_from_stdin() {
+    let output = run(&["-d", "-", "--no-summary", "t", "elom"], DICT);
+
+    assert!(output.status.success());
+    assert_eq!("* 12 motel\n  1  tome\n  1  tote\n", stdout_of(&output));
+}
+
+#[test]
+fn reads_dictionary_from_file() {
+    let dict = temp_file("file-dict.txt", DICT);
+    let output = run(
+        &["-d", dict.to_str().unwrap(), "--no-summary", "t", "elom"],
+        "",
+    );
+    std::fs::remove_file(dict).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!("* 12 motel\n  1  tome\n  1  tote\n", stdout_of(&output));
+}
+
+#[test]
+fn prints_summary_after_answers() {
+    let output = run(&["-d", "-", "t", "elom"], DICT);
+
+    assert!(output.status.success());
+    assert_eq!(
+        "* 12 motel\n  1  tome\n  1  tote\nFound 3 words (1 pangram) for a total of 14 points\n",
+        stdout_of(&output)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn prints_json_answers() {
+    let output = run(
+        &["-d", "-", "--format", "json", "t", "elom"],
+        "motel\ntote\n",
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        serde_json::json!([
+            {"word": "motel", "score": 12, "is_pangram": true},
+            {"word": "tote", "score": 1, "is_pangram": false},
+        ]),
+        json
+    );
+}
+
+#[test]
+fn filters_by_min_score_and_top() {
+    let dict = "tote\nmotel\ntomtom\nmote\n";
+
+    let output = run(
+        &["-d", "-", "--no-summary", "--min-score", "2", "t", "elom"],
+        dict,
+    );
+    assert_eq!("* 12 motel\n  6  tomtom\n", stdout_of(&output));
+
+    let output = run(
+        &["-d", "-", "--no-summary", "--top", "3", "t", "elom"],
+        dict,
+    );
+    assert_eq!("* 12 motel\n  6  tomtom\n  1  mote\n", stdout_of(&output));
+
+    let output = run(
+        &["-d", "-", "--no-summary", "--top", "0", "t", "elom"],
+        dict,
+    );
+    assert_eq!("", stdout_of(&output));
+}
+
+#[test]
+fn rejects_non_letter_puzzle() {
+    let output = run(&["-d", "-", "t", "el3m"], DICT);
+
+    assert!(!output.status.success());
+    assert_eq!("", stdout_of(&output));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'3' is not a letter"));
+}
+
+#[test]
+fn warns_when_required_letter_is_extra() {
+    let output = run(&["-d", "-", "--no-summary", "t", "telom"], "tote\n");
+
+    assert!(output.status.success());
+    assert_eq!("  1  tote\n", stdout_of(&output));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning"));
+}
+
+#[test]
+fn accepts_combined_puzzle_letters() {
+    let output = run(&["-d", "-", "--no-summary", "--puzzle", "telomic"], DICT);
+
+    assert!(output.status.success());
+    assert_eq!("  5  motel\n  1  tome\n  1  tote\n", stdout_of(&output));
+}
+
+#[test]
+fn ignores_non_letters_in_combined_puzzle() {
+    let output = run(
+        &[
+            "-d",
+            "-",
+            "--no-summary",
+            "--puzzle",
+            "\"t\u{200d}e\u{a0}lomic.\"",
+        ],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("  5  motel\n  1  tome\n  1  tote\n", stdout_of(&output));
+}
+
+#[test]
+fn rejects_combined_puzzle_with_wrong_letter_count() {
+    let output = run(&["-d", "-", "--puzzle", "telom"], DICT);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("expected 7 puzzle letters"));
+}
+
+#[test]
+fn groups_answers_by_length() {
+    let output = run(
+        &["-d", "-", "--no-summary", "--by-length", "t", "elom"],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "4 letters:\n  1  tome\n  1  tote\n5 letters:\n* 12 motel\n",
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn prints_two_letter_hints() {
+    let dict = "tote\ntome\ntotem\nteem\nmote\nmotel\nelm\n";
+    let output = run(&["-d", "-", "--no-summary", "--hints", "t", "elom"], dict);
+
+    assert!(output.status.success());
+    assert_eq!("mo: 2\nte: 1, to: 3\n", stdout_of(&output));
+}
+
+#[test]
+fn sorts_answers() {
+    let dict = "tote\nmotel\ntomtom\nmote\n";
+
+    let output = run(&["-d", "-", "--no-summary", "t", "elom"], dict);
+    assert_eq!(
+        "* 12 motel\n  6  tomtom\n  1  mote\n  1  tote\n",
+        stdout_of(&output)
+    );
+
+    let output = run(
+        &["-d", "-", "--no-summary", "--sort", "alpha", "t", "elom"],
+        dict,
+    );
+    assert_eq!(
+        "  1  mote\n* 12 motel\n  6  tomtom\n  1  tote\n",
+        stdout_of(&output)
+    );
+
+    let output = run(
+        &["-d", "-", "--no-summary", "--sort", "length", "t", "elom"],
+        dict,
+    );
+    assert_eq!(
+        "  1  mote\n  1  tote\n* 12 motel\n  6  tomtom\n",
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn shows_only_answers_of_exact_length() {
+    let output = run(
+        &[
+            "-d",
+            "-",
+            "--no-summary",
+            "--exact-length",
+            "4",
+            "t",
+            "elom",
+        ],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("  1  tome\n  1  tote\n", stdout_of(&output));
+
+    let output = run(
+        &[
+            "-d",
+            "-",
+            "--no-summary",
+            "--exact-length",
+            "8",
+            "t",
+            "elom",
+        ],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("", stdout_of(&output));
+}
+
+#[test]
+fn breaks_ties_with_tie_break_order() {
+    let sorted = |tie_break: &str| {
+        stdout_of(&run(
+            &[
+                "-d",
+                "-",
+                "--no-summary",
+                "--sort",
+                "length",
+                "--tie-break",
+                tie_break,
+                "t",
+                "elom",
+            ],
+            "tote\nlotto\nmote\nmotel\n",
+        ))
+    };
+
+    // "lotto" and "motel" are tied on length, so the tie-break decides which
+    // of them comes first.
+    assert_eq!(
+        "  1  mote\n  1  tote\n  5  lotto\n* 12 motel\n",
+        sorted("alpha")
+    );
+    assert_eq!(
+        "  1  mote\n  1  tote\n* 12 motel\n  5  lotto\n",
+        sorted("pangram")
+    );
+}
+
+#[test]
+fn merges_multiple_dictionaries() {
+    let common = temp_file("common-dict.txt", "tote\nmotel\nvote\n");
+    let extra = temp_file("extra-dict.txt", "tome\ntote\nTelmo\n");
+    let output = run(
+        &[
+            "-d",
+            common.to_str().unwrap(),
+            "-d",
+            extra.to_str().unwrap(),
+            "--no-summary",
+            "t",
+            "elom",
+        ],
+        "",
+    );
+    std::fs::remove_file(common).unwrap();
+    std::fs::remove_file(extra).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!("* 12 motel\n  1  tome\n  1  tote\n", stdout_of(&output));
+}
+
+#[test]
+fn names_dictionary_that_failed_to_load() {
+    let output = run(
+        &["-d", "-", "-d", "no/such/dictionary.txt", "t", "elom"],
+        DICT,
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no/such/dictionary.txt"));
+}
+
+#[test]
+fn excludes_words_from_file() {
+    let exclude = temp_file("exclude.txt", "tote\nsoapy\n");
+    let output = run(
+        &[
+            "--no-summary",
+            "--exclude",
+            exclude.to_str().unwrap(),
+            "-d",
+            "-",
+            "t",
+            "elomv",
+        ],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("  5  motel\n  1  tome\n  1  vote\n", stdout_of(&output));
+}
+
+#[test]
+fn excludes_words_ignoring_case() {
+    let exclude = temp_file("exclude-ci.txt", "MOTEL\n");
+    let output = run(
+        &[
+            "--no-summary",
+            "-i",
+            "--exclude",
+            exclude.to_str().unwrap(),
+            "-d",
+            "-",
+            "t",
+            "elom",
+        ],
+        "Motel\ntome\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!("  1  tome\n", stdout_of(&output));
+}
+
+#[test]
+fn prints_only_answer_count() {
+    let output = run(&["--count", "-d", "-", "t", "elomv"], DICT);
+
+    assert!(output.status.success());
+    assert_eq!("4\n", stdout_of(&output));
+}
+
+#[test]
+fn count_applies_filters() {
+    let output = run(
+        &["--count", "--min-score", "2", "-d", "-", "t", "elomv"],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("1\n", stdout_of(&output));
+}
+
+#[test]
+fn shows_only_pangrams() {
+    let output = run(
+        &["--no-summary", "--pangrams", "-d", "-", "t", "elom"],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("* 12 motel\n", stdout_of(&output));
+}
+
+#[test]
+fn uses_custom_pangram_marker() {
+    let output = run(
+        &["--no-summary", "--marker", "P!", "-d", "-", "t", "elom"],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("P! 12 motel\n   1  tome\n   1  tote\n", stdout_of(&output));
+}
+
+#[test]
+fn explains_answer_scores() {
+    let output = run(&["--no-summary", "--explain", "-d", "-", "t", "elom"], DICT);
+
+    assert!(output.status.success());
+    assert_eq!(
+        "motel: 5 (length) + 7 (pangram) = 12\ntome: 1 (short word) = 1\ntote: 1 (short word) = 1\n",
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn solves_puzzles_interactively() {
+    let dict = temp_file("interactive.txt", DICT);
+    let output = run(
+        &[
+            "--interactive",
+            "--no-summary",
+            "-d",
+            dict.to_str().unwrap(),
+        ],
+        "t elom\nnot a puzzle\n\nv elomt\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "* 12 motel\n  1  tome\n  1  tote\n  1  vote\n",
+        stdout_of(&output)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("error"));
+}
+
+#[test]
+fn rejects_interactive_stdin_dictionary() {
+    let output = run(&["--interactive", "-d", "-"], DICT);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn shows_answers_with_prefix() {
+    let output = run(
+        &[
+            "--no-summary",
+            "--starts-with",
+            "to",
+            "-d",
+            "-",
+            "t",
+            "elom",
+        ],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("  1  tome\n  1  tote\n", stdout_of(&output));
+}
+
+#[test]
+fn shows_answers_with_prefix_ignoring_case() {
+    let output = run(
+        &[
+            "--no-summary",
+            "--ignore-case",
+            "--starts-with",
+            "TO",
+            "-d",
+            "-",
+            "t",
+            "elom",
+        ],
+        "Tote\nmotel\ntome\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!("  1  Tote\n  1  tome\n", stdout_of(&output));
+}
+
+#[test]
+fn shows_answers_with_required_count() {
+    let output = run(
+        &[
+            "--no-summary",
+            "--required-count",
+            "2",
+            "-d",
+            "-",
+            "t",
+            "elom",
+        ],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("  1  tote\n", stdout_of(&output));
+}
+
+#[test]
+fn shows_words_needed_for_genius() {
+    let found = temp_file("found.txt", "tote\n");
+    let output = run(
+        &[
+            "--no-summary",
+            "--exclude",
+            found.to_str().unwrap(),
+            "--current-score",
+            "1",
+            "-d",
+            "-",
+            "t",
+            "elom",
+        ],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("* 12 motel\n", stdout_of(&output));
+}
+
+#[test]
+fn prints_anagram_groups() {
+    let output = run(
+        &[
+            "--no-summary",
+            "--anagrams",
+            "--sort",
+            "alpha",
+            "-d",
+            "-",
+            "t",
+            "elom",
+        ],
+        "tome\nmote\ntote\nmotel\nmetol\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!("metol, motel\nmote, tome\n", stdout_of(&output));
+}
+
+#[test]
+fn prints_tsv_answers() {
+    let output = run(&["-d", "-", "--format", "tsv", "t", "elom"], DICT);
+
+    assert!(output.status.success());
+    assert_eq!(
+        "word\tscore\tis_pangram\nmotel\t12\ttrue\ntome\t1\tfalse\ntote\t1\tfalse\n",
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn piped_output_has_no_color_by_default() {
+    let output = run(&["-d", "-", "t", "elom"], DICT);
+
+    assert!(output.status.success());
+    assert!(!stdout_of(&output).contains('\x1b'));
+}
+
+#[test]
+fn color_always_highlights_pangrams() {
+    let output = run(
+        &["--no-summary", "--color", "always", "-d", "-", "t", "elom"],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "\x1b[1;33m* 12 motel\x1b[0m\n  1  tome\n  1  tote\n",
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn adds_bonus_points_from_file() {
+    let bonuses = temp_file("bonuses.txt", "tome=4\n\nsoapy = 2\n");
+    let output = run(
+        &[
+            "--no-summary",
+            "--bonus-file",
+            bonuses.to_str().unwrap(),
+            "-d",
+            "-",
+            "t",
+            "elom",
+        ],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!("* 12 motel\n  5  tome\n  1  tote\n", stdout_of(&output));
+}
+
+#[test]
+fn explains_bonus_file_points() {
+    let bonuses = temp_file("explain-bonuses.txt", "tome=4\n");
+    let output = run(
+        &[
+            "--no-summary",
+            "--explain",
+            "--bonus-file",
+            bonuses.to_str().unwrap(),
+            "-d",
+            "-",
+            "t",
+            "elom",
+        ],
+        DICT,
+    );
+    std::fs::remove_file(bonuses).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        "motel: 5 (length) + 7 (pangram) = 12\ntome: 1 (short word) + 4 (bonus file) = 5\ntote: 1 (short word) = 1\n",
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn invalid_bonus_file_is_an_error() {
+    let bonuses = temp_file("bad-bonuses.txt", "tome=4\ntote\n");
+    let output = run(
+        &[
+            "--bonus-file",
+            bonuses.to_str().unwrap(),
+            "-d",
+            "-",
+            "t",
+            "elom",
+        ],
+        DICT,
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("line 2"));
+}
+
+#[test]
+fn validate_prints_dictionary_stats() {
+    let dict = temp_file("validate-dict.txt", "tote\n\ncan't\nmotels\nbe\n");
+    let output = run(&["-d", dict.to_str().unwrap(), "validate"], "");
+
+    assert!(output.status.success());
+    assert_eq!(
+        format!(
+            "{}:\n  lines: 5\n  blank lines: 1\n  invalid UTF-8 lines: none\n  \
+             entries with non-letters: 1\n  shortest word: be\n  longest word: motels\n",
+            dict.display()
+        ),
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn prints_difficulty() {
+    let output = run(&["--difficulty", "-d", "-", "t", "elom"], DICT);
+
+    assert!(output.status.success());
+    assert_eq!("hard\n", stdout_of(&output));
+}
+
+#[test]
+fn streamed_answers_match_find_all() {
+    let dict =
+        std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/data/words.txt")).unwrap();
+    let output = run(&["--stream", "-d", "-", "e", "arstln"], &dict);
+
+    let expected: Vec<String> = spellingbee::find_all(dict.lines(), 'e', "arstln")
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+
+    assert!(output.status.success());
+    assert!(!expected.is_empty());
+    assert_eq!(expected, stdout_of(&output).lines().collect::<Vec<_>>());
+}
+
+#[test]
+fn stream_rejects_answer_filters() {
+    for filter in [
+        &["--pangrams"][..],
+        &["--min-score", "5"],
+        &["--color", "never"],
+    ] {
+        let mut args = vec!["--stream", "-d", "-"];
+        args.extend_from_slice(filter);
+        args.extend_from_slice(&["t", "elom"]);
+
+        let output = run(&args, DICT);
+        assert!(!output.status.success(), "{:?} was accepted", filter);
+        assert_eq!("", stdout_of(&output));
+    }
+}
+
+#[test]
+fn lists_near_misses_after_answers() {
+    let output = run(
+        &["--no-summary", "--near-misses", "-d", "-", "t", "elom"],
+        "tote\nvote\nmotel\nmole\nmove\nsoapy\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "* 12 motel\n  1  tote\nNear misses:\n  vote\n  mole\n",
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn empty_extra_letters_warns_and_solves() {
+    let output = run(&["--no-summary", "-d", "-", "t", ""], "tttt\ntote\n");
+
+    assert!(output.status.success());
+    assert_eq!("* 8  tttt\n", stdout_of(&output));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning: no extra letters"));
+}
+
+#[cfg(feature = "progress")]
+#[test]
+fn progress_is_hidden_when_not_a_terminal() {
+    let dict = temp_file("progress-dict.txt", DICT);
+    let path = dict.to_str().unwrap();
+
+    let plain = run(&["-d", path, "t", "elom"], "");
+    let output = run(&["--progress", "-d", path, "t", "elom"], "");
+
+    assert!(output.status.success());
+    assert_eq!(stdout_of(&plain), stdout_of(&output));
+    assert!(output.stderr.is_empty());
+}
+
+#[cfg(not(feature = "progress"))]
+#[test]
+fn progress_needs_feature() {
+    let output = run(&["--progress", "-d", "-", "t", "elom"], DICT);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("progress support was not enabled"));
+}
+
+#[test]
+fn shows_letters_used_column() {
+    let output = run(
+        &["--no-summary", "--letters-used", "-d", "-", "t", "elom"],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "* 12 motel (5 letters)\n  1  tome (4 letters)\n  1  tote (3 letters)\n",
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn reads_words_from_csv_column() {
+    let csv = "vote,tote\nmotel,soapy\ntome,tome\n";
+    let second = run(
+        &["--no-summary", "--csv-column", "2", "-d", "-", "t", "elom"],
+        csv,
+    );
+
+    assert!(second.status.success());
+    assert_eq!("  1  tome\n  1  tote\n", stdout_of(&second));
+
+    let first = run(
+        &["--no-summary", "--csv-column", "1", "-d", "-", "t", "elom"],
+        csv,
+    );
+    assert_eq!("* 12 motel\n  1  tome\n", stdout_of(&first));
+}
+
+#[test]
+fn csv_column_starts_at_one() {
+    let output = run(&["--csv-column", "0", "-d", "-", "t", "elom"], DICT);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn stderr_is_silent_by_default() {
+    let output = run(&["-d", "-", "t", "elom"], DICT);
+
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn verbose_prints_word_and_answer_counts() {
+    let output = run(&["-v", "-d", "-", "t", "elom"], DICT);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(output.status.success());
+    assert!(stderr.contains("loaded 5 words"));
+    assert!(stderr.contains("found 3 answers"));
+    assert!(!stderr.contains("rejected"));
+}
+
+#[test]
+fn very_verbose_prints_rejection_reasons() {
+    let output = run(&["-vv", "-d", "-", "t", "elom"], DICT);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(stderr.contains("rejected vote: 'v' is not a puzzle letter"));
+    assert!(stderr.contains("rejected soapy: 's' is not a puzzle letter"));
+}
+
+#[test]
+fn clipboard_conflicts_with_puzzle_letters() {
+    let output = run(&["--clipboard", "-d", "-", "t", "elom"], DICT);
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[cfg(not(feature = "clipboard"))]
+#[test]
+fn clipboard_needs_feature() {
+    let output = run(&["--clipboard", "-d", "-"], DICT);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("clipboard support was not enabled"));
+}
+
+#[test]
+fn diff_shows_only_new_answers() {
+    let previous = temp_file("previous.txt", "tote\nmotel\n");
+    let output = run(
+        &[
+            "--diff",
+            previous.to_str().unwrap(),
+            "-d",
+            "-",
+            "t",
+            "elomv",
+        ],
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "  1  tome\n  1  vote\n2 new words\nFound 2 words (0 pangrams) for a total of 2 points\n",
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn output_file_matches_stdout() {
+    let path = temp_file("output.txt", "");
+    let args = ["-d", "-", "t", "elomv"];
+
+    let expected = run(&args, DICT);
+    let output = run(
+        &[&args[..], &["--output", path.to_str().unwrap()]].concat(),
+        DICT,
+    );
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        stdout_of(&expected),
+        std::fs::read_to_string(&path).unwrap()
+    );
+}
+
+#[test]
+fn output_file_that_cannot_be_created() {
+    let path = std::env::temp_dir()
+        .join("spellingbee-missing-dir")
+        .join("out.txt");
+    let output = run(
+        &["--output", path.to_str().unwrap(), "-d", "-", "t", "elom"],
+        DICT,
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to create output file"));
+}
+
+#[test]
+fn letter_bonus_with_min_score_keeps_rare_letter_words() {
+    // "zest" and "test" are both four letter words worth one point, but only
+    // "zest" is worth enough with the bonus for its rare letter.
+    let output = run(
+        &[
+            "-d",
+            "-",
+            "--no-summary",
+            "--letter-bonus",
+            "z=5",
+            "--min-score",
+            "3",
+            "e",
+            "stzloa",
+        ],
+        "zest\ntest\nzeal\nseat\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!("  6  zeal\n  6  zest\n", stdout_of(&output));
+}
+
+#[test]
+fn letter_bonus_changes_explained_scores() {
+    let output = run(
+        &[
+            "-d",
+            "-",
+            "--no-summary",
+            "--explain",
+            "--letter-bonus",
+            "z=5",
+            "e",
+            "stzloa",
+        ],
+        "zest\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "zest: 1 (short word) + 5 (letters) = 6\n",
+        stdout_of(&output)
+    );
+}
+
+#[test]
+fn stream_strips_leading_bom() {
+    let output = run(
+        &["--stream", "-d", "-", "t", "elom"],
+        "\u{feff}tote\nmotel\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!("  1  tote\n* 12 motel\n", stdout_of(&output));
+}
+
+#[test]
+fn generate_with_seed_is_repeatable() {
+    let dict = "unpopular\nemoticon\ntelomic\nmotel\n";
+    let args = ["-d", "-", "generate", "--seed", "7"];
+
+    for _ in 0..2 {
+        let output = run(&args, dict);
+
+        assert!(output.status.success());
+        assert_eq!("o alnpru\n", stdout_of(&output));
+    }
+}
+
+#[test]
+fn generate_prints_answers() {
+    let output = run(
+        &[
+            "-d",
+            "-",
+            "--no-summary",
+            "generate",
+            "--seed",
+            "3",
+            "--answers",
+        ],
+        "unpopular\n",
+    );
+
+    assert!(output.status.success());
+    assert!(stdout_of(&output).ends_with("* 16 unpopular\n"));
+}
+
+#[test]
+fn generate_without_candidates() {
+    let output = run(&["-d", "-", "generate"], DICT);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("seven different letters"));
+}